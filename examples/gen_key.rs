@@ -10,5 +10,11 @@ fn main() -> Result<()> {
     let b64u = general_purpose::URL_SAFE_NO_PAD.encode(key);
     println!("\nKey b64u encoded:\n{b64u}");
 
+    let mut wallet_key = [0u8; 32]; // 256 bits = 32 bytes (AES-256-GCM)
+    rand::thread_rng().fill_bytes(&mut wallet_key);
+
+    let b64u = general_purpose::URL_SAFE_NO_PAD.encode(wallet_key);
+    println!("\nWallet key b64u encoded (SERVICE_WALLET_KEYS entry):\n{b64u}");
+
     Ok(())
 }
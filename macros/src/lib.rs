@@ -0,0 +1,151 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr, Type};
+
+/// Derive `FromSuiMoveStruct` for a struct mirroring a Move object.
+///
+/// Each field is read from the Move field of the same name, or the one given
+/// with `#[sui_move(rename = "..")]`. The conversion follows the field type
+/// (`String` reads a Move string, integers a number) unless one of
+/// `#[sui_move(uid)]`, `#[sui_move(address)]`, `#[sui_move(number)]` or
+/// `#[sui_move(string)]` is set.
+///
+/// ```ignore
+/// #[derive(FromSuiMoveStruct)]
+/// pub struct SuiHeroObject {
+///     #[sui_move(uid)]
+///     pub id: String,
+///     pub level: u32,
+///     #[sui_move(address)]
+///     pub game_id: String,
+/// }
+/// ```
+#[proc_macro_derive(FromSuiMoveStruct, attributes(sui_move))]
+pub fn derive_from_sui_move_struct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// region:    --- Expand
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input,
+            "FromSuiMoveStruct only supports structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &input,
+            "FromSuiMoveStruct needs named fields",
+        ));
+    };
+
+    let mut field_inits = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.as_ref().expect("named field");
+        let attr = FieldAttr::parse(field)?;
+
+        let move_name = attr.rename.unwrap_or_else(|| ident.to_string());
+        let kind = match attr.kind {
+            Some(kind) => kind,
+            None => Kind::infer(&field.ty),
+        };
+        let convert = kind.converter();
+
+        field_inits.push(quote! {
+            #ident: crate::sui_call::sui_move_object::value::#convert(&field_map, #move_name)?
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics crate::sui_call::sui_move_object::FromSuiMoveStruct for #name #ty_generics #where_clause {
+            fn from_sui_move_struct(
+                field_map: ::std::collections::BTreeMap<String, ::sui_json_rpc_types::SuiMoveValue>,
+            ) -> crate::sui_call::sui_move_object::Result<Self> {
+                Ok(#name {
+                    #(#field_inits,)*
+                })
+            }
+        }
+    })
+}
+// endregion: --- Expand
+
+// region:    --- Field Attributes
+
+enum Kind {
+    Uid,
+    Address,
+    Number,
+    String,
+}
+
+impl Kind {
+    fn infer(ty: &Type) -> Self {
+        match ty {
+            Type::Path(path) if path.path.is_ident("String") => Kind::String,
+            _ => Kind::Number,
+        }
+    }
+
+    fn converter(&self) -> syn::Ident {
+        let name = match self {
+            Kind::Uid => "uid",
+            Kind::Address => "address",
+            Kind::Number => "number",
+            Kind::String => "string",
+        };
+
+        syn::Ident::new(name, proc_macro2::Span::call_site())
+    }
+}
+
+#[derive(Default)]
+struct FieldAttr {
+    rename: Option<String>,
+    kind: Option<Kind>,
+}
+
+impl FieldAttr {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let mut attr = FieldAttr::default();
+
+        for a in field.attrs.iter().filter(|a| a.path().is_ident("sui_move")) {
+            a.parse_nested_meta(|meta| {
+                let kind = if meta.path.is_ident("rename") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    attr.rename = Some(value.value());
+                    return Ok(());
+                } else if meta.path.is_ident("uid") {
+                    Kind::Uid
+                } else if meta.path.is_ident("address") {
+                    Kind::Address
+                } else if meta.path.is_ident("number") {
+                    Kind::Number
+                } else if meta.path.is_ident("string") {
+                    Kind::String
+                } else {
+                    return Err(meta.error("unknown sui_move attribute"));
+                };
+
+                if attr.kind.replace(kind).is_some() {
+                    return Err(meta.error("only one sui_move conversion per field"));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(attr)
+    }
+}
+// endregion: --- Field Attributes
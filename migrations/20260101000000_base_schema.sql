@@ -0,0 +1,40 @@
+-- Base app schema
+--
+-- Deployments older than the migrations already have these tables (from
+-- sql/dev_inits), so they are only created when missing and brought up to date below.
+
+-- User
+CREATE TABLE IF NOT EXISTS "user" (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  username varchar(128) NULL UNIQUE,
+  email varchar(255) NULL UNIQUE,
+  pwd varchar(256) NULL
+);
+
+
+CREATE TABLE IF NOT EXISTS "discord_profile" (
+    id BIGINT PRIMARY KEY REFERENCES "user"(id),
+    discord_id BIGINT NOT NULL UNIQUE,
+    username VARCHAR(35) NOT NULL,
+    global_name VARCHAR(35) NOT NULL,
+    avatar VARCHAR(255) NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS "wallet" (
+    id BIGINT PRIMARY KEY REFERENCES "user"(id),
+    pub_key VARCHAR(100) NOT NULL,
+    sign_type VARCHAR(10) NOT NULL,
+    phrase TEXT NOT NULL, -- encrypted envelope, see crypt::encrypt_phrase
+    last_faucet BIGINT NULL
+);
+
+CREATE TABLE IF NOT EXISTS "bot" (
+    bot_id VARCHAR(100) PRIMARY KEY,
+    user_id BIGINT REFERENCES "user" (id),
+    last_creation BIGINT NULL
+);
+
+-- Bring a pre-migrations schema up to date, no-ops on a fresh one
+-- The phrase envelope does not fit the former VARCHAR(255), see crypt::encrypt_phrase
+ALTER TABLE "wallet" ALTER COLUMN phrase TYPE TEXT;
+CREATE UNIQUE INDEX IF NOT EXISTS discord_profile_discord_id_key ON "discord_profile" (discord_id);
@@ -0,0 +1,41 @@
+-- Indexer
+CREATE TABLE "indexer_cursor" (
+    name VARCHAR(64) PRIMARY KEY,
+    tx_digest VARCHAR(64) NOT NULL,
+    event_seq BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL
+);
+
+-- Game events, keyed by the sui event id so a replay never duplicates a row
+CREATE TABLE "battle_event" (
+    tx_digest VARCHAR(64) NOT NULL,
+    event_seq BIGINT NOT NULL,
+    sender VARCHAR(100) NOT NULL,
+    pet_id VARCHAR(100) NOT NULL,
+    bot_id VARCHAR(100) NOT NULL,
+    pet_won BOOLEAN NOT NULL,
+    timestamp_ms BIGINT NULL,
+    PRIMARY KEY (tx_digest, event_seq)
+);
+
+CREATE TABLE "pet_level_up_event" (
+    tx_digest VARCHAR(64) NOT NULL,
+    event_seq BIGINT NOT NULL,
+    sender VARCHAR(100) NOT NULL,
+    pet_id VARCHAR(100) NOT NULL,
+    level BIGINT NOT NULL,
+    timestamp_ms BIGINT NULL,
+    PRIMARY KEY (tx_digest, event_seq)
+);
+
+CREATE TABLE "bot_created_event" (
+    tx_digest VARCHAR(64) NOT NULL,
+    event_seq BIGINT NOT NULL,
+    sender VARCHAR(100) NOT NULL,
+    bot_id VARCHAR(100) NOT NULL,
+    owner VARCHAR(100) NOT NULL,
+    hp BIGINT NOT NULL,
+    strength BIGINT NOT NULL,
+    timestamp_ms BIGINT NULL,
+    PRIMARY KEY (tx_digest, event_seq)
+);
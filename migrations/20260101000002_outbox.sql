@@ -0,0 +1,19 @@
+-- Outbox of game actions, executed by the worker (see worker::Worker)
+CREATE TABLE "outbox" (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+    idempotency_key VARCHAR(128) NOT NULL UNIQUE,
+    user_id BIGINT NULL REFERENCES "user"(id),
+    action TEXT NOT NULL, -- json, see worker::GameAction
+    status VARCHAR(16) NOT NULL DEFAULT 'pending',
+    attempts INT NOT NULL DEFAULT 0,
+    max_attempts INT NOT NULL,
+    next_attempt_at BIGINT NOT NULL,
+    locked_until BIGINT NULL,
+    digest VARCHAR(64) NULL,
+    result TEXT NULL,
+    last_error TEXT NULL,
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL
+);
+
+CREATE INDEX outbox_pending_idx ON "outbox" (next_attempt_at) WHERE status = 'pending';
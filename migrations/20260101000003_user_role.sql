@@ -0,0 +1,2 @@
+-- Role of the user, see ctx::Role and models::access
+ALTER TABLE "user" ADD COLUMN IF NOT EXISTS role VARCHAR(16) NOT NULL DEFAULT 'player';
@@ -0,0 +1,14 @@
+-- Audit log of the row changes and chain transactions, see models::audit_log
+CREATE TABLE "audit_log" (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+    actor_id BIGINT NOT NULL, -- ctx user, 0 for the server (root ctx)
+    user_id BIGINT NULL, -- player owning the row or the transaction, kept after a delete
+    entity VARCHAR(64) NOT NULL,
+    entity_id VARCHAR(100) NOT NULL,
+    action VARCHAR(16) NOT NULL,
+    diff TEXT NULL, -- json, {field: {before, after}}
+    tx_digest VARCHAR(64) NULL,
+    created_at BIGINT NOT NULL
+);
+
+CREATE INDEX audit_log_user_idx ON "audit_log" (user_id, id);
@@ -2,13 +2,20 @@
 
 mod db;
 
+use std::str::FromStr;
+use std::sync::Arc;
+
+use sui_types::base_types::{ObjectID, SuiAddress};
 use tokio::sync::OnceCell;
 use tracing::info;
 
+use crate::get_config;
+use crate::sui_call::call_api::{GameKeystore, SuiExecutor};
+use crate::sui_call::gateway::{FakeGateway, FAKE_FAUCET_AMOUNT};
 use crate::{ctx::Ctx, models::ModelManager};
 // endregion: -- Modules
 
-/// init db for dev
+/// Drop and re-create the dev database, `DB_RESET` or tests only.
 pub async fn init_db() {
     static INIT: OnceCell<()> = OnceCell::const_new();
 
@@ -33,3 +40,31 @@ pub async fn init_db_for_test() -> ModelManager {
 
     mm.clone()
 }
+
+/// for testing only, an executor on a new in-memory sui network with the
+/// game deployed at the config ids (`PACKAGE`, `GAME_INFO_ID`, `GAME_ADMIN_ID`).
+pub fn init_sui_for_test() -> (Arc<FakeGateway>, SuiExecutor) {
+    let config = get_config();
+
+    let package_id = ObjectID::from_str(&config.PACKAGE).unwrap();
+    let admin = SuiAddress::from_str(&config.SUI_CLIENT_ADDRESS).unwrap();
+    let gateway = Arc::new(FakeGateway::new(package_id));
+    gateway.publish(
+        admin,
+        ObjectID::from_str(&config.GAME_INFO_ID).unwrap(),
+        ObjectID::from_str(&config.GAME_ADMIN_ID).unwrap(),
+    );
+    // -- For the onboarding top-ups.
+    gateway.add_coin(admin, 10 * FAKE_FAUCET_AMOUNT);
+
+    // -- The fake does not check signatures, the keystore stays empty.
+    let keystore_path = tempfile::tempdir()
+        .unwrap()
+        .into_path()
+        .join("sui.keystore");
+    let keystore = GameKeystore::file(&keystore_path).unwrap();
+
+    let executor = SuiExecutor::new(gateway.clone(), package_id, keystore);
+
+    (gateway, executor)
+}
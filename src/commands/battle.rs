@@ -1,145 +1,195 @@
-use std::path::Path;
 use std::str::FromStr;
 
+use serenity::async_trait;
 use serenity::builder;
 use serenity::model::application::interaction::application_command::{
-    CommandDataOption, CommandDataOptionValue,
+    ApplicationCommandInteraction, CommandDataOption,
 };
 use serenity::model::prelude::command::CommandOptionType;
-use shared_crypto::intent::Intent;
-use sui_json_rpc_types::{SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions};
-use sui_keys::keystore::{self, AccountKeystore, FileBasedKeystore, Keystore};
-use sui_sdk::json::SuiJsonValue;
-use sui_sdk::SuiClient;
 use sui_types::base_types::{ObjectID, SuiAddress};
-use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
-use sui_types::transaction::{Transaction, TransactionData};
+use sui_types::digests::TransactionDigest;
 use tracing::debug;
 
+use super::state::get_game_state;
+use super::{
+    get_object_id_option, run_game_action, ActionProgress, CommandResponse, Error, Result,
+    SlashCommand, MAX_AUTOCOMPLETE_CHOICES,
+};
+use crate::event_handler::Handler;
 use crate::get_config;
-use crate::sui_call::call_api::create_bot::get_object_id;
+use crate::models::user::UserInfo;
+use crate::sui_call::call_api::{MoveCall, SuiExecutor};
+use crate::sui_call::gateway::SuiGateway;
 use crate::sui_call::read_api::owned_objects::WePetGame;
+use crate::sui_call::sui_move_object::bot_obj::SuiBotObject;
 use crate::sui_call::sui_move_object::hero_obj::SuiHeroObject;
-use crate::sui_call::{HERO_OBJECT_NAME, MODULE_NAME};
+use crate::sui_call::sui_move_object::pet_obj::SuiPetObject;
+use crate::sui_call::{BOT_OBJECT_NAME, HERO_OBJECT_NAME, PET_OBJECT_NAME};
+use crate::utils::truncate_hex_string;
+use crate::worker::GameAction;
 
-// TODO: implement function
 pub async fn do_battle(
-    sui_client: &SuiClient,
-    package_object_id: &ObjectID,
-    // keystore: &Keystore,
-    options: &[CommandDataOption],
+    executor: &SuiExecutor,
     signer: SuiAddress,
-) -> Result<(), anyhow::Error> {
-    let keystore_path = Path::new("/home/ganzzi/.sui/sui_config/sui.keystore");
-    let mut keystore =
-        Keystore::from(FileBasedKeystore::new(&keystore_path.to_path_buf()).unwrap());
-
+    pet: ObjectID,
+    bot: ObjectID,
+) -> Result<TransactionDigest> {
     let wepet_game = WePetGame::new(
-        sui_client.clone(),
+        executor.gateway().clone(),
         signer,
-        package_object_id.to_string().as_str(),
+        executor.package_id().to_string().as_str(),
     );
     let hero = wepet_game
         .get_sui_obj_first::<SuiHeroObject>(HERO_OBJECT_NAME)
         .await
-        .ok()
-        .unwrap();
-
-    println!("hero data: \n{hero:?}\n");
-
-    let option = get_option(options, 0).unwrap();
-    let option2 = get_option(options, 1).unwrap();
-
-    println!("option data: \n{option:?}\n");
-    println!("option2 data: \n{option2:?}\n");
-
-    // FIXME:  MODULE_NAME, FUNCTION_NAME
-    if let (CommandDataOptionValue::String(pet), CommandDataOptionValue::String(bot)) =
-        (option, option2)
-    {
-        let config = get_config();
-
-        // FIXME:  MODULE_NAME, FUNCTION_NAME
-
-        let transaction_data = sui_client
-            .transaction_builder()
-            .move_call(
-                signer,
-                package_object_id.clone(),
-                MODULE_NAME,
-                "huntbot",
-                vec![],
-                vec![
-                    SuiJsonValue::from_str(config.GAME_INFO_ID.as_str()).unwrap(),
-                    SuiJsonValue::from_str(hero.id.as_str()).unwrap(),
-                    SuiJsonValue::from_str(pet.as_str()).unwrap(),
-                    SuiJsonValue::from_str(bot.as_str()).unwrap(),
-                ],
-                None,
-                300000000,
-            )
-            .await
-            .map_err(|e| {
-                println!("{e:?}");
-                anyhow::Error::msg("sui transaction fail")
-            })?;
-
-        // Sign transaction.
-        let signature = keystore
-            .sign_secure(&signer, &transaction_data, Intent::sui_transaction())
-            .map_err(|e| debug!("{e:?}"))
-            .unwrap();
-
-        // Execute the transaction.
-
-        let response: SuiTransactionBlockResponse = sui_client
-            .quorum_driver_api()
-            .execute_transaction_block(
-                Transaction::from_data(
-                    transaction_data,
-                    Intent::sui_transaction(),
-                    vec![signature],
-                ),
-                SuiTransactionBlockResponseOptions::full_content(),
-                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
-            )
-            .await
-            .map_err(|e| println!("{e:?}"))
-            .unwrap();
-
-        Ok(())
-    } else {
-        Err(anyhow::Error::msg("error message"))
-    }
+        .map_err(|_| Error::ObjectNotFound(HERO_OBJECT_NAME))?;
+
+    debug!("battle - hero: {hero:?} - pet: {pet} - bot: {bot}");
+
+    let config = get_config();
+
+    let call = MoveCall::new("huntbot")
+        .object(&config.GAME_INFO_ID)
+        .object(&hero.id)
+        .object(pet)
+        .object(bot);
+
+    let effects = executor.execute(signer, call).await?;
+
+    Ok(effects.digest)
 }
 
-fn get_option(options: &[CommandDataOption], index: usize) -> Option<&CommandDataOptionValue> {
-    let option = options
-        .get(index)
-        .expect("Expected arg option")
-        .resolved
-        .as_ref();
-    option
+pub struct BattleCommand;
+
+#[async_trait]
+impl SlashCommand for BattleCommand {
+    fn name(&self) -> &'static str {
+        "battle"
+    }
+
+    fn register<'a>(
+        &self,
+        command: &'a mut builder::CreateApplicationCommand,
+    ) -> &'a mut builder::CreateApplicationCommand {
+        command
+            .description("Command for battle ...")
+            .create_option(|option| {
+                option
+                    .name("pet")
+                    .description("Your pet")
+                    .kind(CommandOptionType::String)
+                    .set_autocomplete(true)
+                    .required(true)
+            })
+            .create_option(|option| {
+                option
+                    .name("bot")
+                    .description("choose bot to battle")
+                    .kind(CommandOptionType::String)
+                    .set_autocomplete(true)
+                    .required(true)
+            })
+    }
+
+    fn defer(&self) -> bool {
+        true
+    }
+
+    async fn run(
+        &self,
+        handler: &Handler,
+        command: &ApplicationCommandInteraction,
+        user_info: Option<&UserInfo>,
+    ) -> Result<CommandResponse> {
+        let user_info = user_info.ok_or(Error::NotAPlayer)?;
+
+        let signer = SuiAddress::from_str(&user_info.wallet.pub_key)
+            .map_err(|e| Error::SuiRpc(e.to_string()))?;
+
+        if !check_sui(handler.executor.gateway().as_ref(), signer).await {
+            return Err(Error::NoSuiCoin);
+        }
+
+        let pet = get_object_id_option(&command.data.options, "pet")?;
+        let bot = get_object_id_option(&command.data.options, "bot")?;
+
+        let action = GameAction::Battle { signer, pet, bot };
+        match run_game_action(handler, command, user_info, action).await? {
+            ActionProgress::Done(_) => {
+                let state = get_game_state(handler, user_info).await?;
+
+                Ok(CommandResponse::board(&state, None))
+            }
+            ActionProgress::Queued(entry) => Ok(CommandResponse::queued("battle", &entry)),
+        }
+    }
+
+    async fn autocomplete(
+        &self,
+        handler: &Handler,
+        option: &CommandDataOption,
+        user_info: &UserInfo,
+    ) -> Result<Vec<(String, String)>> {
+        let player = SuiAddress::from_str(&user_info.wallet.pub_key)
+            .map_err(|e| Error::SuiRpc(e.to_string()))?;
+        let wepet_game = WePetGame::new(
+            handler.executor.gateway().clone(),
+            player,
+            handler.package_id.to_string().as_str(),
+        );
+
+        let choices: Vec<(String, String)> = match option.name.as_str() {
+            "pet" => wepet_game
+                .get_sui_objs::<SuiPetObject>(PET_OBJECT_NAME)
+                .await?
+                .into_iter()
+                .map(|pet| {
+                    let name = format!(
+                        "{} - Hp: {} Strength: {}",
+                        truncate_hex_string(&pet.id, 6),
+                        pet.hp,
+                        pet.strength
+                    );
+                    (name, pet.id)
+                })
+                .collect(),
+            "bot" => wepet_game
+                .get_sui_objs::<SuiBotObject>(BOT_OBJECT_NAME)
+                .await?
+                .into_iter()
+                .map(|bot| {
+                    let name = format!(
+                        "{} - Hp: {} Strength: {}",
+                        truncate_hex_string(&bot.id, 6),
+                        bot.hp,
+                        bot.strength
+                    );
+                    (name, bot.id)
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        // -- Filter by what the player typed so far.
+        let typed = option
+            .value
+            .as_ref()
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        Ok(choices
+            .into_iter()
+            .filter(|(_, id)| id.to_lowercase().contains(&typed))
+            .take(MAX_AUTOCOMPLETE_CHOICES)
+            .collect())
+    }
 }
 
-pub fn register(
-    command: &mut builder::CreateApplicationCommand,
-) -> &mut builder::CreateApplicationCommand {
-    command
-        .name("battle")
-        .description("Command for battle ...")
-        .create_option(|option| {
-            option
-                .name("pet")
-                .description("Your pet")
-                .kind(CommandOptionType::String)
-                .required(true)
-        })
-        .create_option(|option| {
-            option
-                .name("bot")
-                .description("choose bot to battle")
-                .kind(CommandOptionType::String)
-                .required(true)
-        })
+async fn check_sui(gateway: &dyn SuiGateway, address: SuiAddress) -> bool {
+    gateway
+        .get_coins(address)
+        .await
+        .map(|coins| !coins.is_empty())
+        .unwrap_or(false)
 }
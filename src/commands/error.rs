@@ -0,0 +1,248 @@
+use serde::{Deserialize, Serialize};
+use sui_sdk::error::Error as SuiError;
+use sui_types::base_types::ObjectIDParseError;
+
+use crate::sui_call::call_api::{self, GameError};
+use crate::{commands, game_state, models, worker};
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, Serialize, strum_macros::AsRefStr)]
+#[serde(tag = "type", content = "data")]
+pub enum Error {
+    // -- Player
+    NotAPlayer,
+    NoSuiCoin,
+    NotEnoughCoin { needed: u64 },
+    NoGasCoin,
+
+    // -- Options
+    MissingOption(&'static str),
+    BadOption { name: &'static str, value: String },
+
+    // -- Objects
+    ObjectNotFound(&'static str),
+    NotOwner { object: String },
+
+    // -- Sui
+    SuiRpc(String),
+    SuiCall(call_api::Error),
+
+    // -- Worker
+    ActionFailed { id: i64, client_error: ClientError },
+    Worker(String),
+
+    // -- Modules
+    Model(models::Error),
+    GameState(game_state::Error),
+}
+
+// region:    --- Froms
+impl From<models::Error> for Error {
+    fn from(val: models::Error) -> Self {
+        Self::Model(val)
+    }
+}
+
+impl From<game_state::Error> for Error {
+    fn from(val: game_state::Error) -> Self {
+        Self::GameState(val)
+    }
+}
+
+impl From<call_api::Error> for Error {
+    fn from(val: call_api::Error) -> Self {
+        Self::SuiCall(val)
+    }
+}
+
+impl From<worker::Error> for Error {
+    fn from(val: worker::Error) -> Self {
+        Self::Worker(val.to_string())
+    }
+}
+
+impl From<SuiError> for Error {
+    fn from(val: SuiError) -> Self {
+        Self::SuiRpc(val.to_string())
+    }
+}
+
+// `sui_call` and the sui transaction builder return `anyhow` errors.
+impl From<anyhow::Error> for Error {
+    fn from(val: anyhow::Error) -> Self {
+        Self::SuiRpc(val.to_string())
+    }
+}
+
+impl From<ObjectIDParseError> for Error {
+    fn from(val: ObjectIDParseError) -> Self {
+        Self::BadOption {
+            name: "object id",
+            value: val.to_string(),
+        }
+    }
+}
+// endregion: --- Froms
+
+// region:    --- Error Boilerplate
+impl core::fmt::Display for Error {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        write!(fmt, "{self:?}")
+    }
+}
+
+impl std::error::Error for Error {}
+// endregion: --- Error Boilerplate
+
+// region:    --- Client Error
+
+/// From the command error to the ClientError shown to the player
+impl Error {
+    pub fn client_error(&self) -> ClientError {
+        use commands::Error::*;
+
+        #[allow(unreachable_patterns)]
+        match self {
+            // -- Player
+            NotAPlayer => ClientError::NOT_A_PLAYER,
+            NoSuiCoin => ClientError::NO_SUI_COIN,
+            NotEnoughCoin { .. } => ClientError::NOT_ENOUGH_COIN,
+            NoGasCoin => ClientError::NO_GAS_COIN,
+
+            // -- Options
+            MissingOption(name) => ClientError::INVALID_OPTION(name.to_string()),
+            BadOption { name, .. } => ClientError::INVALID_OPTION(name.to_string()),
+
+            // -- Objects
+            ObjectNotFound(object) => ClientError::OBJECT_NOT_FOUND(object.to_string()),
+            NotOwner { object } => ClientError::NOT_OWNER(object.clone()),
+
+            // -- Sui
+            SuiCall(e) => Self::sui_call_client_error(e),
+
+            // -- Worker
+            ActionFailed { client_error, .. } => client_error.clone(),
+
+            // -- Model
+            Model(models::Error::EntityNotFound { .. }) => ClientError::NOT_A_PLAYER,
+            Model(models::Error::AccessDenied(_)) => ClientError::NO_PERMISSION,
+
+            // -- Fallback.
+            _ => ClientError::SERVICE_ERROR,
+        }
+    }
+
+    pub fn sui_call_client_error(error: &call_api::Error) -> ClientError {
+        match error {
+            call_api::Error::DryRunFail { reason, .. } => {
+                ClientError::ACTION_REJECTED(reason.clone())
+            }
+            call_api::Error::TransactionFail { .. } => ClientError::TRANSACTION_FAIL,
+            call_api::Error::GameRejected { game_error, .. } => ClientError::from(*game_error),
+            _ => ClientError::SERVICE_ERROR,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, strum_macros::AsRefStr)]
+#[serde(tag = "message", content = "detail")]
+#[allow(non_camel_case_types)]
+pub enum ClientError {
+    NOT_A_PLAYER,
+    NO_SUI_COIN,
+    NOT_ENOUGH_COIN,
+    NO_GAS_COIN,
+    INVALID_OPTION(String),
+    OBJECT_NOT_FOUND(String),
+    NOT_OWNER(String),
+    ACTION_REJECTED(String),
+    PET_FAINTED,
+    BOT_ALREADY_DEFEATED,
+    INSUFFICIENT_LEVEL,
+    TRANSACTION_FAIL,
+    NO_PERMISSION,
+
+    SERVICE_ERROR,
+}
+
+impl ClientError {
+    /// Message shown to the player in discord.
+    pub fn message(&self) -> String {
+        match self {
+            ClientError::NOT_A_PLAYER => "You are not a player yet, use `/register` first.".into(),
+            ClientError::NO_SUI_COIN => {
+                "You have no SUI coin, request some from the faucet first.".into()
+            }
+            ClientError::NOT_ENOUGH_COIN => "You don't have enough SUI coin for this.".into(),
+            ClientError::NO_GAS_COIN => {
+                "You need a second SUI coin to pay the gas, request one from the faucet.".into()
+            }
+            ClientError::INVALID_OPTION(name) => format!("Invalid `{name}`, please check it."),
+            ClientError::OBJECT_NOT_FOUND(object) => format!("Your {object} was not found."),
+            ClientError::NOT_OWNER(object) => format!("You don't own this {object}."),
+            ClientError::ACTION_REJECTED(reason) => {
+                format!("The game rejected this action: {reason}.")
+            }
+            ClientError::PET_FAINTED => "Your pet has fainted, let it rest first.".into(),
+            ClientError::BOT_ALREADY_DEFEATED => "This bot was already defeated.".into(),
+            ClientError::INSUFFICIENT_LEVEL => {
+                "Your hero's level is too low for this, level up first.".into()
+            }
+            ClientError::TRANSACTION_FAIL => "The transaction failed on chain.".into(),
+            ClientError::NO_PERMISSION => "You are not allowed to do this.".into(),
+            ClientError::SERVICE_ERROR => "Something went wrong, please try again.".into(),
+        }
+    }
+}
+
+impl From<GameError> for ClientError {
+    fn from(val: GameError) -> Self {
+        match val {
+            GameError::NotOwner => ClientError::NOT_OWNER("object".to_string()),
+            GameError::NotEnoughCoin => ClientError::NOT_ENOUGH_COIN,
+            GameError::PetFainted => ClientError::PET_FAINTED,
+            GameError::BotAlreadyDefeated => ClientError::BOT_ALREADY_DEFEATED,
+            GameError::InsufficientLevel => ClientError::INSUFFICIENT_LEVEL,
+        }
+    }
+}
+// endregion: --- Client Error
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_error_mapping() {
+        let e = Error::BadOption {
+            name: "pet",
+            value: "0xzz".to_string(),
+        };
+        assert!(matches!(e.client_error(), ClientError::INVALID_OPTION(name) if name == "pet"));
+
+        let e = Error::Model(models::Error::EntityNotFound {
+            entity: "discord_profile",
+            id: 1,
+        });
+        assert!(matches!(e.client_error(), ClientError::NOT_A_PLAYER));
+
+        // -- Internal errors must not leak to the player.
+        let e = Error::SuiCall(call_api::Error::Signing {
+            signer: "0x0".to_string(),
+            reason: "bad key".to_string(),
+        });
+        assert!(matches!(e.client_error(), ClientError::SERVICE_ERROR));
+        assert!(!e.client_error().message().contains("bad key"));
+
+        // -- Decoded aborts become their own reply.
+        let e = Error::SuiCall(call_api::Error::GameRejected {
+            function: "huntbot",
+            game_error: GameError::BotAlreadyDefeated,
+        });
+        assert!(matches!(
+            e.client_error(),
+            ClientError::BOT_ALREADY_DEFEATED
+        ));
+    }
+}
@@ -0,0 +1,102 @@
+use serenity::async_trait;
+use serenity::builder::CreateApplicationCommand;
+use serenity::model::application::interaction::application_command::{
+    ApplicationCommandInteraction, CommandDataOption, CommandDataOptionValue,
+};
+use serenity::model::prelude::command::CommandOptionType;
+use serenity::model::Permissions;
+
+use super::{get_option, CommandResponse, Error, Result, SlashCommand};
+use crate::ctx::{Ctx, Role};
+use crate::event_handler::Handler;
+use crate::models::audit_log::{AuditLog, AuditLogBmc};
+use crate::models::user::UserInfo;
+use crate::models::{ListOptions, UserBmc};
+
+// Entries shown, a discord message is at most 2000 characters.
+const HISTORY_LIMIT: i64 = 15;
+
+/// Admin command, the latest audit log entries of a player.
+pub struct HistoryCommand;
+
+#[async_trait]
+impl SlashCommand for HistoryCommand {
+    fn name(&self) -> &'static str {
+        "history"
+    }
+
+    fn register<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand {
+        command
+            .description("Admin - history of a player.")
+            .default_member_permissions(Permissions::ADMINISTRATOR)
+            .create_option(|option| {
+                option
+                    .name("player")
+                    .description("the player")
+                    .kind(CommandOptionType::User)
+                    .required(true)
+            })
+    }
+
+    async fn run(
+        &self,
+        handler: &Handler,
+        command: &ApplicationCommandInteraction,
+        user_info: Option<&UserInfo>,
+    ) -> Result<CommandResponse> {
+        let user_info = user_info.ok_or(Error::NotAPlayer)?;
+
+        // -- The model layer checks the invoking user is an admin.
+        let ctx = Ctx::new(
+            user_info.base_info.id,
+            Role::from_db(&user_info.base_info.role),
+        )
+        .map_err(|_| Error::NotAPlayer)?;
+
+        let discord_id = get_user_option(&command.data.options, "player")?;
+        let player =
+            UserBmc::get_user_info_by_discord_id(&Ctx::root_ctx(), &handler.mm, discord_id).await?;
+
+        let options = ListOptions {
+            limit: Some(HISTORY_LIMIT),
+            ..Default::default()
+        };
+        let logs =
+            AuditLogBmc::list_by_user(&ctx, &handler.mm, player.base_info.id, options).await?;
+
+        let username = player.discord.username;
+        if logs.is_empty() {
+            return Ok(format!("No history for {username}.").into());
+        }
+
+        let lines: Vec<String> = logs.iter().map(history_line).collect();
+
+        Ok(format!("History of {username}:\n{}", lines.join("\n")).into())
+    }
+}
+
+fn get_user_option(options: &[CommandDataOption], name: &'static str) -> Result<i64> {
+    match get_option(options, name)? {
+        CommandDataOptionValue::User(user, _) => Ok(user.id.0 as i64),
+        value => Err(Error::BadOption {
+            name,
+            value: format!("{value:?}"),
+        }),
+    }
+}
+
+fn history_line(log: &AuditLog) -> String {
+    let tx = log
+        .tx_digest
+        .as_deref()
+        .map(|digest| format!(" tx `{digest}`"))
+        .unwrap_or_default();
+
+    format!(
+        "`#{}` <t:{}:R> {} {} `{}` by {}{tx}",
+        log.id, log.created_at, log.action, log.entity, log.entity_id, log.actor_id
+    )
+}
@@ -1,83 +1,287 @@
+use std::pin::pin;
 use std::str::FromStr;
 
-use anyhow::Error;
-use serde_json::{json, Number, Value as JsonValue};
+use serenity::async_trait;
 use serenity::builder;
-use serenity::model::application::interaction::application_command::{
-    CommandDataOption, CommandDataOptionValue,
-};
+use serenity::futures::StreamExt;
+use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
 use serenity::model::prelude::command::CommandOptionType;
-use std::env;
-use sui_sdk::json::SuiJsonValue;
+use sui_sdk::error::Error as SuiError;
 use sui_sdk::types::base_types::{ObjectID, SuiAddress};
-use sui_sdk::types::transaction::TransactionData;
-use sui_sdk::SuiClient;
+use sui_sdk::types::digests::TransactionDigest;
+use tracing::{debug, warn};
+
+use super::state::get_game_state;
+use super::{
+    get_integer_option, get_object_id_option, run_game_action, ActionProgress, CommandResponse,
+    Error, Result, SlashCommand,
+};
+use crate::event_handler::Handler;
+use crate::get_config;
+use crate::models::user::UserInfo;
+use crate::sui_call::call_api::{MoveCall, SuiExecutor};
+use crate::sui_call::read_api::owned_objects::WePetGame;
+use crate::sui_call::sui_move_object;
+use crate::sui_call::sui_move_object::hero_obj::SuiHeroObject;
+use crate::sui_call::sui_move_object::pet_obj::SuiPetObject;
+use crate::sui_call::{HERO_OBJECT_NAME, PET_OBJECT_NAME};
+use crate::worker::GameAction;
+
+const HUNT_FUNCTION_NAME: &str = "hunt";
+
+// 1 coin staked on `/hunt` is 0.01 SUI (in MIST).
+const HUNT_COIN_UNIT: u64 = 10_000_000;
+const HUNT_MAX_COIN: u64 = 100;
+
+// region:    --- Hunt Outcome
+/// The `_after` fields are `None` when the pet could not be read back after
+/// the hunt landed.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct HuntOutcome {
+    pub digest: String,
+    pub stake: i64,
+    pub exp_gained: Option<i64>,
+    pub hp_before: u32,
+    pub hp_after: Option<u32>,
+    pub strength_before: u32,
+    pub strength_after: Option<u32>,
+}
+
+impl HuntOutcome {
+    fn new(
+        digest: TransactionDigest,
+        stake: i64,
+        before: &SuiPetObject,
+        after: Option<&SuiPetObject>,
+    ) -> Self {
+        HuntOutcome {
+            digest: digest.to_string(),
+            stake,
+            exp_gained: after.map(|after| after.exp as i64 - before.exp as i64),
+            hp_before: before.hp,
+            hp_after: after.map(|after| after.hp),
+            strength_before: before.strength,
+            strength_after: after.map(|after| after.strength),
+        }
+    }
+}
+
+impl core::fmt::Display for HuntOutcome {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        match (self.exp_gained, self.hp_after, self.strength_after) {
+            (Some(exp_gained), Some(hp_after), Some(strength_after)) => write!(
+                fmt,
+                "Hunt finished! staked: {} coin -------- exp: {:+}\nHp: {} -> {} -------- Strength: {} -> {}",
+                self.stake,
+                exp_gained,
+                self.hp_before,
+                hp_after,
+                self.strength_before,
+                strength_after
+            ),
+            _ => write!(
+                fmt,
+                "Hunt finished! staked: {} coin\nYour pet is back, transaction {}",
+                self.stake, self.digest
+            ),
+        }
+    }
+}
+// endregion: --- Hunt Outcome
 
 pub async fn do_hunt(
-    sui_client: &SuiClient,
-    package_object_id: &ObjectID,
-    options: &[CommandDataOption],
+    executor: &SuiExecutor,
     signer: SuiAddress,
-) -> Result<TransactionData, anyhow::Error> {
-    let option = get_option(options, 0).unwrap();
-    let option2 = get_option(options, 1).unwrap();
-
-    // FIXME:  MODULE_NAME, FUNCTION_NAME
-    if let (CommandDataOptionValue::String(arg_1), CommandDataOptionValue::Integer(arg_2)) =
-        (option, option2)
-    {
-        let a: Result<TransactionData, anyhow::Error> = sui_client
-            .transaction_builder()
-            .move_call(
-                signer,
-                package_object_id.clone(),
-                "MODULE_NAME",
-                "FUNCTION_NAME",
-                vec![],
-                vec![
-                    SuiJsonValue::from_str(arg_1)?,
-                    SuiJsonValue::new(json!(arg_2))?,
-                ],
-                None,
-                1000,
-            )
-            .await;
-
-        a
-    } else {
-        Err(Error::msg("error message"))
+    animal: ObjectID,
+    coin: i64,
+) -> Result<HuntOutcome> {
+    // -- The coin comes back from the outbox json, checked again before the stake.
+    let stake = u64::try_from(coin)
+        .ok()
+        .filter(|coin| (1..=HUNT_MAX_COIN).contains(coin))
+        .ok_or(Error::BadOption {
+            name: "coin",
+            value: coin.to_string(),
+        })?
+        * HUNT_COIN_UNIT;
+
+    let config = get_config();
+    let wepet_game = WePetGame::new(
+        executor.gateway().clone(),
+        signer,
+        executor.package_id().to_string().as_str(),
+    );
+
+    // -- Validate the pet against the player's owned pets.
+    let pet_before = find_pet(&wepet_game, &animal)
+        .await?
+        .ok_or(Error::NotOwner {
+            object: PET_OBJECT_NAME.to_string(),
+        })?;
+
+    let hero = wepet_game
+        .get_sui_obj_first::<SuiHeroObject>(HERO_OBJECT_NAME)
+        .await
+        .map_err(|_| Error::ObjectNotFound(HERO_OBJECT_NAME))?;
+
+    // -- Validate the stake against the player's SUI coins.
+    let mut coins = executor
+        .gateway()
+        .get_coins(signer)
+        .await
+        .map_err(|e| Error::SuiRpc(e.to_string()))?;
+    coins.sort_by_key(|c| c.balance);
+    // -- The smallest coin holding the stake, another coin pays the gas.
+    let stake_coin = coins
+        .iter()
+        .find(|c| c.balance >= stake)
+        .ok_or(Error::NotEnoughCoin { needed: stake })?;
+    if coins.len() < 2 {
+        return Err(Error::NoGasCoin);
+    }
+
+    let call = MoveCall::new(HUNT_FUNCTION_NAME)
+        .object(&config.GAME_INFO_ID)
+        .object(&hero.id)
+        .object(animal)
+        .object(stake_coin.coin_object_id)
+        .u64(stake);
+
+    let effects = executor.execute(signer, call).await?;
+
+    // -- The hunt landed, a failed read must not fail (and retry) it.
+    let pet_after = match find_pet(&wepet_game, &animal).await {
+        Ok(pet) => pet,
+        Err(e) => {
+            warn!("hunt - {} - pet not read back: {e:?}", effects.digest);
+            None
+        }
+    };
+
+    Ok(HuntOutcome::new(
+        effects.digest,
+        coin,
+        &pet_before,
+        pet_after.as_ref(),
+    ))
+}
+
+async fn find_pet(wepet_game: &WePetGame, animal: &ObjectID) -> Result<Option<SuiPetObject>> {
+    let pets = wepet_game.get_sui_objs_stream::<SuiPetObject>(PET_OBJECT_NAME);
+    let mut pets = pin!(pets);
+
+    while let Some(pet) = pets.next().await {
+        match pet {
+            Ok(pet) if ObjectID::from_str(&pet.id).ok().as_ref() == Some(animal) => {
+                return Ok(Some(pet))
+            }
+            Ok(_) => (),
+            // -- A page request failed, the pet may be on it.
+            Err(e) if e.is::<SuiError>() => return Err(e.into()),
+            // -- The pet struct no longer matches the move package.
+            Err(e) if e.is::<sui_move_object::Error>() => return Err(e.into()),
+            Err(e) => debug!("hunt - skip pet: {e:?}"),
+        }
     }
+
+    Ok(None)
 }
 
-fn get_option(options: &[CommandDataOption], index: usize) -> Option<&CommandDataOptionValue> {
-    let option = options
-        .get(index)
-        .expect("Expected user option")
-        .resolved
-        .as_ref();
-    option
+pub struct HuntCommand;
+
+#[async_trait]
+impl SlashCommand for HuntCommand {
+    fn name(&self) -> &'static str {
+        "hunt"
+    }
+
+    fn register<'a>(
+        &self,
+        command: &'a mut builder::CreateApplicationCommand,
+    ) -> &'a mut builder::CreateApplicationCommand {
+        command
+            .description("Send your pet hunting, staking some coin")
+            .create_option(|option| {
+                option
+                    .name("animal")
+                    .description("your pet id")
+                    .kind(CommandOptionType::String)
+                    .required(true)
+            })
+            .create_option(|option| {
+                option
+                    .name("coin")
+                    .description("coin to stake (1 coin = 0.01 SUI)")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(1)
+                    .max_int_value(HUNT_MAX_COIN)
+                    .required(true)
+            })
+    }
+
+    fn defer(&self) -> bool {
+        true
+    }
+
+    async fn run(
+        &self,
+        handler: &Handler,
+        command: &ApplicationCommandInteraction,
+        user_info: Option<&UserInfo>,
+    ) -> Result<CommandResponse> {
+        let user_info = user_info.ok_or(Error::NotAPlayer)?;
+
+        let animal = get_object_id_option(&command.data.options, "animal")?;
+        let coin = get_integer_option(&command.data.options, "coin")?;
+        let signer = SuiAddress::from_str(&user_info.wallet.pub_key)
+            .map_err(|e| Error::SuiRpc(e.to_string()))?;
+
+        let action = GameAction::Hunt {
+            signer,
+            animal,
+            coin,
+        };
+        match run_game_action(handler, command, user_info, action).await? {
+            ActionProgress::Done(entry) => {
+                let outcome = entry
+                    .result
+                    .as_deref()
+                    .and_then(|result| serde_json::from_str::<HuntOutcome>(result).ok())
+                    .map(|outcome| outcome.to_string());
+
+                let state = get_game_state(handler, user_info).await?;
+
+                Ok(CommandResponse::board(&state, outcome))
+            }
+            ActionProgress::Queued(entry) => Ok(CommandResponse::queued("hunt", &entry)),
+        }
+    }
 }
 
-pub fn register(
-    command: &mut builder::CreateApplicationCommand,
-) -> &mut builder::CreateApplicationCommand {
-    command
-        .name("hunt")
-        .description("Test command for number input")
-        .create_option(|option| {
-            option
-                .name("animal")
-                .description("animal id")
-                .kind(CommandOptionType::String)
-                .required(true)
-        })
-        .create_option(|option| {
-            option
-                .name("coin")
-                .description("coin to hunt")
-                .kind(CommandOptionType::Integer)
-                .min_int_value(1)
-                .max_int_value(100)
-                .required(true)
-        })
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use dotenvy::dotenv;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+
+    use super::do_hunt;
+    use crate::_dev_init;
+    use crate::commands::Error;
+
+    #[tokio::test]
+    async fn test_do_hunt_bad_coin() {
+        dotenv().ok();
+
+        let (_, executor) = _dev_init::init_sui_for_test();
+
+        for coin in [0, 101, -1, i64::MIN] {
+            let res = do_hunt(&executor, SuiAddress::ZERO, ObjectID::ZERO, coin).await;
+
+            assert!(matches!(
+                res,
+                Err(Error::BadOption { name: "coin", value }) if value == coin.to_string()
+            ));
+        }
+    }
 }
+// endregion: --- Tests
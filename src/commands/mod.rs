@@ -1,2 +1,278 @@
+// region:    --- Modules
 pub mod battle;
+mod error;
+pub mod history;
 pub mod hunt;
+pub mod register;
+pub mod state;
+
+use serenity::async_trait;
+use serenity::builder::{CreateApplicationCommand, CreateEmbed};
+use serenity::model::application::interaction::application_command::{
+    ApplicationCommandInteraction, CommandDataOption, CommandDataOptionValue,
+};
+use std::str::FromStr;
+use std::time::Duration;
+use sui_types::base_types::ObjectID;
+
+pub use self::error::{ClientError, Error, Result};
+use crate::ctx::Ctx;
+use crate::event_handler::Handler;
+use crate::game_state::UserGameState;
+use crate::get_config;
+use crate::models::outbox::{OutboxEntry, OutboxStatus};
+use crate::models::user::UserInfo;
+use crate::worker::{self, GameAction};
+// endregion: --- Modules
+
+// Discord accepts at most 25 autocomplete choices.
+pub const MAX_AUTOCOMPLETE_CHOICES: usize = 25;
+
+// region:    --- Slash Command
+/// A discord slash command.
+///
+/// The same implementation drives both the global command registration
+/// (`Handler::ready`) and the dispatch (`Handler::interaction_create`).
+#[async_trait]
+pub trait SlashCommand: Send + Sync {
+    /// Command name, used for registration and dispatch.
+    fn name(&self) -> &'static str;
+
+    /// Description and options of the command (the name is already set).
+    fn register<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand;
+
+    /// Whether the invoking user must be a registered player.
+    fn require_player(&self) -> bool {
+        true
+    }
+
+    /// Whether the response is deferred (the command calls into `sui_call`).
+    ///
+    /// Deferred commands are acknowledged right away and the original response
+    /// is edited once `run` returns.
+    fn defer(&self) -> bool {
+        false
+    }
+
+    /// Run the command. `user_info` is `Some` when the user is a registered player.
+    async fn run(
+        &self,
+        handler: &Handler,
+        command: &ApplicationCommandInteraction,
+        user_info: Option<&UserInfo>,
+    ) -> Result<CommandResponse>;
+
+    /// Autocomplete choices `(name, value)` for the focused `option`.
+    async fn autocomplete(
+        &self,
+        _handler: &Handler,
+        _option: &CommandDataOption,
+        _user_info: &UserInfo,
+    ) -> Result<Vec<(String, String)>> {
+        Ok(Vec::new())
+    }
+}
+// endregion: --- Slash Command
+
+// region:    --- Option Helpers
+pub fn get_option<'a>(
+    options: &'a [CommandDataOption],
+    name: &'static str,
+) -> Result<&'a CommandDataOptionValue> {
+    options
+        .iter()
+        .find(|option| option.name == name)
+        .and_then(|option| option.resolved.as_ref())
+        .ok_or(Error::MissingOption(name))
+}
+
+pub fn get_object_id_option(options: &[CommandDataOption], name: &'static str) -> Result<ObjectID> {
+    match get_option(options, name)? {
+        CommandDataOptionValue::String(value) => {
+            ObjectID::from_str(value).map_err(|_| Error::BadOption {
+                name,
+                value: value.clone(),
+            })
+        }
+        value => Err(Error::BadOption {
+            name,
+            value: format!("{value:?}"),
+        }),
+    }
+}
+
+pub fn get_integer_option(options: &[CommandDataOption], name: &'static str) -> Result<i64> {
+    match get_option(options, name)? {
+        CommandDataOptionValue::Integer(value) => Ok(*value),
+        value => Err(Error::BadOption {
+            name,
+            value: format!("{value:?}"),
+        }),
+    }
+}
+// endregion: --- Option Helpers
+
+// region:    --- Game Action
+
+/// Where a queued game action stands when the command answers.
+pub enum ActionProgress {
+    Done(OutboxEntry),
+    /// Still queued or retrying after `OUTBOX_COMMAND_WAIT_SEC`.
+    Queued(OutboxEntry),
+}
+
+/// Queue the game action of the interaction and wait for the worker.
+///
+/// The interaction id is the idempotency key, a redelivered interaction
+/// waits on the action already queued.
+pub async fn run_game_action(
+    handler: &Handler,
+    command: &ApplicationCommandInteraction,
+    user_info: &UserInfo,
+    action: GameAction,
+) -> Result<ActionProgress> {
+    let ctx = Ctx::root_ctx();
+    let config = get_config();
+
+    let id = worker::enqueue(
+        &ctx,
+        &handler.mm,
+        format!("discord:{}", command.id),
+        Some(user_info.base_info.id),
+        &action,
+    )
+    .await?;
+
+    let entry = worker::wait_for(
+        &ctx,
+        &handler.mm,
+        id,
+        Duration::from_secs(config.OUTBOX_COMMAND_WAIT_SEC),
+    )
+    .await?;
+
+    if entry.is(OutboxStatus::Done) {
+        Ok(ActionProgress::Done(entry))
+    } else if entry.is(OutboxStatus::Failed) {
+        let client_error = entry
+            .result
+            .as_deref()
+            .and_then(|result| serde_json::from_str(result).ok())
+            .unwrap_or(ClientError::SERVICE_ERROR);
+
+        Err(Error::ActionFailed {
+            id: entry.id,
+            client_error,
+        })
+    } else {
+        Ok(ActionProgress::Queued(entry))
+    }
+}
+// endregion: --- Game Action
+
+// region:    --- Command Response
+pub enum CommandResponse {
+    Text(String),
+
+    /// Embed reply, `fallback` is sent instead in DMs and used for logs.
+    Embed {
+        content: Option<String>,
+        embed: CreateEmbed,
+        fallback: String,
+    },
+}
+
+impl CommandResponse {
+    /// Game state board, optionally preceded by a message (e.g. a battle result).
+    pub fn board(state: &UserGameState, content: Option<String>) -> Self {
+        let board = state.get_game_state_board();
+        let fallback = match &content {
+            Some(content) => format!("{content}\n{board}"),
+            None => board,
+        };
+
+        CommandResponse::Embed {
+            content,
+            embed: state.get_game_state_embed(),
+            fallback,
+        }
+    }
+
+    /// Progress of an action that did not finish while the command waited.
+    pub fn queued(action: &str, entry: &OutboxEntry) -> Self {
+        CommandResponse::Text(format!(
+            "Your {action} is queued (action #{}, attempt {}/{}), check `/state` in a moment.",
+            entry.id, entry.attempts, entry.max_attempts
+        ))
+    }
+
+    /// Message content and embed to send, given whether the reply goes to a DM.
+    pub fn into_message(self, is_dm: bool) -> (Option<String>, Option<CreateEmbed>) {
+        match self {
+            CommandResponse::Text(text) => (Some(text), None),
+            CommandResponse::Embed { fallback, .. } if is_dm => (Some(fallback), None),
+            CommandResponse::Embed { content, embed, .. } => (content, Some(embed)),
+        }
+    }
+
+    /// Plain text version of the response, for logs.
+    pub fn as_text(&self) -> &str {
+        match self {
+            CommandResponse::Text(text) => text,
+            CommandResponse::Embed { fallback, .. } => fallback,
+        }
+    }
+}
+
+impl From<String> for CommandResponse {
+    fn from(text: String) -> Self {
+        CommandResponse::Text(text)
+    }
+}
+
+impl From<&str> for CommandResponse {
+    fn from(text: &str) -> Self {
+        CommandResponse::Text(text.to_string())
+    }
+}
+// endregion: --- Command Response
+
+// region:    --- Command Registry
+pub struct CommandRegistry {
+    commands: Vec<Box<dyn SlashCommand>>,
+}
+
+impl CommandRegistry {
+    pub fn new() -> Self {
+        CommandRegistry {
+            commands: vec![
+                Box::new(register::RegisterCommand),
+                Box::new(state::StateCommand),
+                Box::new(battle::BattleCommand),
+                Box::new(hunt::HuntCommand),
+                Box::new(history::HistoryCommand),
+            ],
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn SlashCommand> {
+        self.commands
+            .iter()
+            .find(|command| command.name() == name)
+            .map(|command| command.as_ref())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn SlashCommand> {
+        self.commands.iter().map(|command| command.as_ref())
+    }
+}
+
+impl Default for CommandRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+// endregion: --- Command Registry
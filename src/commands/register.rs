@@ -0,0 +1,68 @@
+use std::str::FromStr;
+
+use serenity::async_trait;
+use serenity::builder::CreateApplicationCommand;
+use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
+use sui_types::base_types::SuiAddress;
+
+use super::{CommandResponse, Error, Result, SlashCommand};
+use crate::ctx::Ctx;
+use crate::event_handler::Handler;
+use crate::models::user::UserInfo;
+use crate::worker;
+
+pub struct RegisterCommand;
+
+#[async_trait]
+impl SlashCommand for RegisterCommand {
+    fn name(&self) -> &'static str {
+        "register"
+    }
+
+    fn register<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand {
+        command.description("register to play...")
+    }
+
+    fn require_player(&self) -> bool {
+        false
+    }
+
+    async fn run(
+        &self,
+        handler: &Handler,
+        _command: &ApplicationCommandInteraction,
+        user_info: Option<&UserInfo>,
+    ) -> Result<CommandResponse> {
+        // -- A partial account (onboarding not done) is completed.
+        if let Some(user_info) = user_info {
+            let player = SuiAddress::from_str(&user_info.wallet.pub_key)
+                .map_err(|e| Error::SuiRpc(e.to_string()))?;
+            let resumed = worker::resume_register(
+                &Ctx::root_ctx(),
+                &handler.mm,
+                user_info.base_info.id,
+                player,
+            )
+            .await?;
+
+            return Ok(if resumed {
+                "Your account is being completed, your hero, pet and first bot are on their way."
+                    .into()
+            } else {
+                "Player already exist".into()
+            });
+        }
+
+        let r_uri = handler
+            .config
+            .CLOUDFLARE_SERVER_URL
+            .strip_prefix("https://")
+            .unwrap_or_default()
+            .to_string();
+
+        Ok(CommandResponse::Text(format!("Enter this link to authorize and register: https://discord.com/api/oauth2/authorize?client_id=1172504182691991562&redirect_uri=https%3A%2F%2F{}%2Fauth%2Fregister&response_type=code&scope=identify", r_uri)))
+    }
+}
@@ -0,0 +1,53 @@
+use serenity::async_trait;
+use serenity::builder::CreateApplicationCommand;
+use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
+
+use super::{CommandResponse, Error, Result, SlashCommand};
+use crate::event_handler::Handler;
+use crate::game_state::UserGameState;
+use crate::models::user::UserInfo;
+
+pub struct StateCommand;
+
+#[async_trait]
+impl SlashCommand for StateCommand {
+    fn name(&self) -> &'static str {
+        "state"
+    }
+
+    fn register<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand {
+        command.description("Get your game state.")
+    }
+
+    fn defer(&self) -> bool {
+        true
+    }
+
+    async fn run(
+        &self,
+        handler: &Handler,
+        _command: &ApplicationCommandInteraction,
+        user_info: Option<&UserInfo>,
+    ) -> Result<CommandResponse> {
+        let user_info = user_info.ok_or(Error::NotAPlayer)?;
+
+        let state = get_game_state(handler, user_info).await?;
+
+        Ok(CommandResponse::board(&state, None))
+    }
+}
+
+pub async fn get_game_state(handler: &Handler, user_info: &UserInfo) -> Result<UserGameState> {
+    let state = UserGameState::new_state(
+        handler.executor.gateway(),
+        &handler.mm,
+        &handler.package_id,
+        user_info,
+    )
+    .await?;
+
+    Ok(state)
+}
@@ -1,5 +1,8 @@
+use crate::crypt::WalletKey;
+use crate::sui_call::call_api::{AbortCatalog, KeystoreBackend};
+use crate::sui_call::network::SuiNetwork;
 use crate::{utils::b64::b64u_decode, Error, Result};
-use std::{env, str::FromStr, sync::OnceLock};
+use std::{collections::HashMap, env, path::PathBuf, str::FromStr, sync::OnceLock};
 
 // public function to get the singleton config
 pub fn get_config() -> &'static Config {
@@ -19,6 +22,9 @@ pub struct Config {
 
     pub SERVICE_DB_URL: String,
 
+    /// Drop and re-create the dev database at startup (never in a deploy).
+    pub DB_RESET: bool,
+
     pub SERVICE_PWD_KEY: Vec<u8>,
 
     pub SERVICE_TOKEN_KEY: Vec<u8>,
@@ -27,6 +33,9 @@ pub struct Config {
 
     pub SERVICE_PASSWORD_SALT: String,
 
+    /// First key is the active one, the others only decrypt (key rotation).
+    pub SERVICE_WALLET_KEYS: Vec<WalletKey>,
+
     pub DISCORD_CLIENT_ID: String,
 
     pub DISCORD_CLIENT_SECRET: String,
@@ -39,11 +48,63 @@ pub struct Config {
 
     pub PACKAGE: String,
 
+    pub SUI_NETWORK: SuiNetwork,
+
     pub SUI_CLIENT_ADDRESS: String,
 
     pub GAME_INFO_ID: String,
 
     pub GAME_ADMIN_ID: String,
+
+    // -- Keystore
+    pub KEYSTORE_BACKEND: KeystoreBackend,
+
+    pub KEYSTORE_PATH: PathBuf,
+
+    pub KEYSTORE_ADMIN_PHRASE: Option<String>,
+
+    // -- Gas budget
+    pub GAS_BUDGET_MARGIN_PCT: u64,
+
+    pub GAS_BUDGET_MAX: u64,
+
+    pub GAS_BUDGET_CEILINGS: HashMap<String, u64>,
+
+    // -- Gas pool (admin signer)
+    /// Gas coins kept for the admin, `0` leaves the gas coin to the sdk.
+    pub GAS_POOL_SIZE: usize,
+
+    pub GAS_POOL_MIN_BALANCE: u64,
+
+    pub GAS_POOL_REBALANCE_SEC: u64,
+
+    pub GAS_POOL_LEASE_TIMEOUT_SEC: u64,
+
+    // -- Game errors
+    /// From `ABORT_CODES_FILE`, or the sui failure statuses only.
+    pub ABORT_CATALOG: AbortCatalog,
+
+    // -- Onboarding
+    /// MIST sent by the admin to a new player with its profile, `0` for none.
+    pub ONBOARD_TOP_UP_MIST: u64,
+
+    // -- Event indexer
+    pub INDEXER_POLL_MS: u64,
+
+    // -- Outbox worker
+    pub OUTBOX_POLL_MS: u64,
+
+    /// Entries executed at the same time.
+    pub OUTBOX_WORKERS: usize,
+
+    pub OUTBOX_MAX_ATTEMPTS: i32,
+
+    pub OUTBOX_BACKOFF_BASE_SEC: i64,
+
+    pub OUTBOX_BACKOFF_MAX_SEC: i64,
+
+    /// How long a discord command waits on its action before reporting it as queued.
+    pub OUTBOX_COMMAND_WAIT_SEC: u64,
 }
 
 impl Config {
@@ -52,21 +113,51 @@ impl Config {
             ENVIRONMENT: get_from_env("ENVIRONMENT")?,
             SERVICE_WEB_FOLDER: get_from_env("SERVICE_WEB_FOLDER")?,
             SERVICE_DB_URL: get_from_env("SERVICE_DB_URL")?,
+            DB_RESET: get_env_parse_or("DB_RESET", false)?,
             SERVICE_PWD_KEY: get_env_b64u_as_u8s("SERVICE_PWD_KEY")?,
             SERVICE_TOKEN_DURATION_SEC: get_env_parse("SERVICE_TOKEN_DURATION_SEC")?,
             SERVICE_TOKEN_KEY: get_env_b64u_as_u8s("SERVICE_TOKEN_KEY")?,
             SERVICE_PASSWORD_SALT: get_from_env("SERVICE_PASSWORD_SALT")?,
+            SERVICE_WALLET_KEYS: get_env_wallet_keys("SERVICE_WALLET_KEYS")?,
             DISCORD_CLIENT_ID: get_from_env("DISCORD_CLIENT_ID")?,
             CLOUDFLARE_SERVER_URL: get_from_env("CLOUDFLARE_SERVER_URL")?,
             DISCORD_CLIENT_SECRET: get_from_env("DISCORD_CLIENT_SECRET")?,
             APPLICATION_ID: get_env_parse("APPLICATION_ID")?,
             PACKAGE: get_env_parse("PACKAGE")?,
+            SUI_NETWORK: get_env_sui_network()?,
             DISCORD_TOKEN: get_env_parse("DISCORD_TOKEN")?,
             SUI_CLIENT_ADDRESS: get_env_parse("SUI_CLIENT_ADDRESS")?,
             GAME_INFO_ID: get_env_parse("GAME_INFO_ID")?,
             GAME_ADMIN_ID: get_env_parse("GAME_ADMIN_ID")?,
+            KEYSTORE_BACKEND: get_env_parse_or("KEYSTORE_BACKEND", KeystoreBackend::File)?,
+            KEYSTORE_PATH: get_env_parse_or("KEYSTORE_PATH", default_keystore_path())?,
+            KEYSTORE_ADMIN_PHRASE: env::var("KEYSTORE_ADMIN_PHRASE").ok(),
+            GAS_BUDGET_MARGIN_PCT: get_env_parse_or("GAS_BUDGET_MARGIN_PCT", 20)?,
+            GAS_BUDGET_MAX: get_env_parse_or("GAS_BUDGET_MAX", 300000000)?,
+            GAS_BUDGET_CEILINGS: get_env_u64_map_or_empty("GAS_BUDGET_CEILINGS")?,
+            GAS_POOL_SIZE: get_env_parse_or("GAS_POOL_SIZE", 0)?,
+            GAS_POOL_MIN_BALANCE: get_env_parse_or("GAS_POOL_MIN_BALANCE", 500000000)?,
+            GAS_POOL_REBALANCE_SEC: get_env_parse_or("GAS_POOL_REBALANCE_SEC", 60)?,
+            GAS_POOL_LEASE_TIMEOUT_SEC: get_env_parse_or("GAS_POOL_LEASE_TIMEOUT_SEC", 30)?,
+            ABORT_CATALOG: get_env_abort_catalog("ABORT_CODES_FILE")?,
+            ONBOARD_TOP_UP_MIST: get_env_parse_or("ONBOARD_TOP_UP_MIST", 0)?,
+            INDEXER_POLL_MS: get_env_parse_or("INDEXER_POLL_MS", 5000)?,
+            OUTBOX_POLL_MS: get_env_parse_or("OUTBOX_POLL_MS", 1000)?,
+            OUTBOX_WORKERS: get_env_parse_or("OUTBOX_WORKERS", 1)?,
+            OUTBOX_MAX_ATTEMPTS: get_env_parse_or("OUTBOX_MAX_ATTEMPTS", 5)?,
+            OUTBOX_BACKOFF_BASE_SEC: get_env_parse_or("OUTBOX_BACKOFF_BASE_SEC", 2)?,
+            OUTBOX_BACKOFF_MAX_SEC: get_env_parse_or("OUTBOX_BACKOFF_MAX_SEC", 60)?,
+            OUTBOX_COMMAND_WAIT_SEC: get_env_parse_or("OUTBOX_COMMAND_WAIT_SEC", 60)?,
         })
     }
+
+    /// Highest gas budget allowed for the move function `function`.
+    pub fn gas_ceiling(&self, function: &str) -> u64 {
+        self.GAS_BUDGET_CEILINGS
+            .get(function)
+            .copied()
+            .unwrap_or(self.GAS_BUDGET_MAX)
+    }
 }
 // endregion: --- Config struct and inplementation
 
@@ -80,6 +171,109 @@ fn get_env_parse<T: FromStr>(name: &'static str) -> Result<T> {
     val.parse::<T>().map_err(|_| Error::WrongFormat(name))
 }
 
+fn get_env_parse_or<T: FromStr>(name: &'static str, default: T) -> Result<T> {
+    match env::var(name) {
+        Ok(val) => val.parse::<T>().map_err(|_| Error::WrongFormat(name)),
+        Err(_) => Ok(default),
+    }
+}
+
+/// Parse a `key=value,key=value` list, e.g. `hunt=50000000,huntbot=80000000`.
+fn get_env_u64_map_or_empty(name: &'static str) -> Result<HashMap<String, u64>> {
+    let Ok(val) = env::var(name) else {
+        return Ok(HashMap::new());
+    };
+
+    val.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (key, value) = entry.split_once('=').ok_or(Error::WrongFormat(name))?;
+            let value = value
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| Error::WrongFormat(name))?;
+            Ok((key.trim().to_string(), value))
+        })
+        .collect()
+}
+
+/// The abort catalog from the json file at `name`, the defaults when unset.
+fn get_env_abort_catalog(name: &'static str) -> Result<AbortCatalog> {
+    let Ok(path) = env::var(name) else {
+        return Ok(AbortCatalog::default());
+    };
+
+    let json = std::fs::read_to_string(path).map_err(|_| Error::WrongFormat(name))?;
+
+    AbortCatalog::from_json(&json).map_err(|_| Error::WrongFormat(name))
+}
+
+/// The sui cli keystore, `~/.sui/sui_config/sui.keystore`.
+fn default_keystore_path() -> PathBuf {
+    let home = env::var("HOME").unwrap_or_default();
+
+    PathBuf::from(home).join(".sui/sui_config/sui.keystore")
+}
+
+/// Parse a `key_id:b64u_key,key_id:b64u_key` list of 32 bytes keys.
+fn get_env_wallet_keys(name: &'static str) -> Result<Vec<WalletKey>> {
+    let keys = get_from_env(name)?
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (id, key) = entry.split_once(':').ok_or(Error::WrongFormat(name))?;
+            let key = b64u_decode(key.trim()).map_err(|_| Error::WrongFormat(name))?;
+            let id = id.trim();
+
+            // The id is written in the `#`-separated envelope.
+            if id.is_empty() || id.contains('#') || key.len() != 32 {
+                return Err(Error::WrongFormat(name));
+            }
+
+            Ok(WalletKey {
+                id: id.to_string(),
+                key,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if keys.is_empty() {
+        return Err(Error::WrongFormat(name));
+    }
+
+    Ok(keys)
+}
+
+/// `SUI_NETWORK` defaults, each endpoint can be overridden (`SUI_RPC_URL` is
+/// required for a network not known by `SuiNetwork::from_name`).
+fn get_env_sui_network() -> Result<SuiNetwork> {
+    let name = env::var("SUI_NETWORK").unwrap_or_else(|_| "devnet".to_string());
+    let rpc_url = env::var("SUI_RPC_URL").ok();
+
+    let mut network = match (SuiNetwork::from_name(&name), rpc_url) {
+        (Some(network), None) => network,
+        (Some(network), Some(rpc_url)) => SuiNetwork { rpc_url, ..network },
+        (None, Some(rpc_url)) => SuiNetwork {
+            name,
+            rpc_url,
+            ws_url: None,
+            faucet_url: None,
+        },
+        (None, None) => return Err(Error::ConfigMissing("SUI_RPC_URL")),
+    };
+
+    if let Ok(ws_url) = env::var("SUI_WS_URL") {
+        network.ws_url = Some(ws_url);
+    }
+    if let Ok(faucet_url) = env::var("SUI_FAUCET_URL") {
+        network.faucet_url = Some(faucet_url);
+    }
+
+    Ok(network)
+}
+
 fn get_env_b64u_as_u8s(name: &'static str) -> Result<Vec<u8>> {
     b64u_decode(&get_from_env(name)?).map_err(|_| Error::WrongFormat(name))
 }
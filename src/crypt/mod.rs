@@ -0,0 +1,225 @@
+// region:    --- Modules
+
+mod error;
+
+pub use self::error::{Error, Result};
+
+use crate::get_config;
+use crate::utils::b64::{b64u_decode, b64u_encode};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+// endregion: --- Modules
+
+// Envelope: `#e1#{key_id}#{b64u(nonce + wrapped data key)}#{b64u(nonce + encrypted content)}`
+const ENVELOPE_SCHEME: &str = "#e1#";
+const NONCE_LEN: usize = 12;
+
+// region:    --- Types
+
+/// A key encryption key from `SERVICE_WALLET_KEYS`.
+///
+/// The id is written in every envelope, so a retired key still decrypts
+/// the rows it sealed until they are re-encrypted.
+#[derive(Clone)]
+pub struct WalletKey {
+    pub id: String,
+    pub key: Vec<u8>,
+}
+
+// endregion: --- Types
+
+// region:    --- Public Functions
+
+/// Encrypt a wallet phrase with a fresh data key, sealed by the active wallet key.
+pub fn encrypt_phrase(phrase: &str) -> Result<String> {
+    encrypt_with(active_key()?, phrase)
+}
+
+/// Decrypt a wallet phrase envelope, with whichever wallet key sealed it.
+pub fn decrypt_phrase(envelope: &str) -> Result<String> {
+    decrypt_with(&get_config().SERVICE_WALLET_KEYS, envelope)
+}
+
+/// Envelope prefix of the active wallet key, rows without it need re-encryption.
+pub fn active_envelope_prefix() -> Result<String> {
+    Ok(envelope_prefix(active_key()?))
+}
+
+/// Re-seal a stored phrase with the active wallet key.
+///
+/// Returns `None` when already sealed by the active key. Plaintext phrases
+/// (rows written before encryption) are encrypted as is.
+pub fn reencrypt_phrase(stored: &str) -> Result<Option<String>> {
+    reencrypt_with(&get_config().SERVICE_WALLET_KEYS, stored)
+}
+// endregion: --- Public Functions
+
+// region: --- Helper Functions
+fn active_key() -> Result<&'static WalletKey> {
+    get_config()
+        .SERVICE_WALLET_KEYS
+        .first()
+        .ok_or(Error::KeyFail)
+}
+
+fn envelope_prefix(key: &WalletKey) -> String {
+    format!("{ENVELOPE_SCHEME}{}#", key.id)
+}
+
+fn encrypt_with(key: &WalletKey, content: &str) -> Result<String> {
+    // -- Seal the content with a fresh data key.
+    let data_key = Aes256Gcm::generate_key(OsRng);
+    let sealed_content = seal(data_key.as_slice(), content.as_bytes())?;
+
+    // -- Seal the data key with the wallet key.
+    let sealed_key = seal(&key.key, data_key.as_slice())?;
+
+    Ok(format!(
+        "{}{}#{}",
+        envelope_prefix(key),
+        b64u_encode(sealed_key),
+        b64u_encode(sealed_content)
+    ))
+}
+
+fn decrypt_with(keys: &[WalletKey], envelope: &str) -> Result<String> {
+    let rest = envelope
+        .strip_prefix(ENVELOPE_SCHEME)
+        .ok_or(Error::NotEncrypted)?;
+
+    let mut parts = rest.splitn(3, '#');
+    let (Some(key_id), Some(sealed_key), Some(sealed_content)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(Error::EnvelopeFormat);
+    };
+
+    let key = keys
+        .iter()
+        .find(|k| k.id == key_id)
+        .ok_or(Error::KeyNotFound(key_id.to_string()))?;
+
+    let sealed_key = b64u_decode(sealed_key).map_err(|_| Error::EnvelopeFormat)?;
+    let sealed_content = b64u_decode(sealed_content).map_err(|_| Error::EnvelopeFormat)?;
+
+    let data_key = open(&key.key, &sealed_key)?;
+    let content = open(&data_key, &sealed_content)?;
+
+    String::from_utf8(content).map_err(|_| Error::DecryptFail)
+}
+
+fn reencrypt_with(keys: &[WalletKey], stored: &str) -> Result<Option<String>> {
+    let active = keys.first().ok_or(Error::KeyFail)?;
+
+    if stored.starts_with(&envelope_prefix(active)) {
+        return Ok(None);
+    }
+
+    let content = match decrypt_with(keys, stored) {
+        Ok(content) => content,
+        Err(Error::NotEncrypted) => stored.to_string(),
+        Err(e) => return Err(e),
+    };
+
+    encrypt_with(active, &content).map(Some)
+}
+
+/// AES-256-GCM, the random nonce is prepended to the cipher text.
+fn seal(key: &[u8], content: &[u8]) -> Result<Vec<u8>> {
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| Error::KeyFail)?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let encrypted = cipher
+        .encrypt(&nonce, content)
+        .map_err(|_| Error::EncryptFail)?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend(encrypted);
+
+    Ok(sealed)
+}
+
+fn open(key: &[u8], sealed: &[u8]) -> Result<Vec<u8>> {
+    if sealed.len() < NONCE_LEN {
+        return Err(Error::EnvelopeFormat);
+    }
+    let (nonce, encrypted) = sealed.split_at(NONCE_LEN);
+
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| Error::KeyFail)?;
+
+    cipher
+        .decrypt(Nonce::from_slice(nonce), encrypted)
+        .map_err(|_| Error::DecryptFail)
+}
+// endregion: --- Helper Functions
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wallet_key(id: &str, byte: u8) -> WalletKey {
+        WalletKey {
+            id: id.to_string(),
+            key: vec![byte; 32],
+        }
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_ok() {
+        let keys = vec![wallet_key("k1", 1)];
+        let phrase = "abandon ability able about above absent";
+
+        let envelope = encrypt_with(&keys[0], phrase).unwrap();
+
+        assert!(envelope.starts_with("#e1#k1#"));
+        assert!(!envelope.contains(phrase));
+        assert_eq!(decrypt_with(&keys, &envelope).unwrap(), phrase);
+    }
+
+    #[test]
+    fn test_decrypt_tampered_fail() {
+        let keys = vec![wallet_key("k1", 1)];
+        let envelope = encrypt_with(&keys[0], "ab cd").unwrap();
+
+        let (prefix, content) = envelope.rsplit_once('#').unwrap();
+        let mut content = b64u_decode(content).unwrap();
+        content[NONCE_LEN] ^= 1;
+        let tampered = format!("{prefix}#{}", b64u_encode(content));
+
+        assert!(matches!(
+            decrypt_with(&keys, &tampered),
+            Err(Error::DecryptFail)
+        ));
+    }
+
+    #[test]
+    fn test_reencrypt_rotation_ok() {
+        let old_keys = vec![wallet_key("k1", 1)];
+        let keys = vec![wallet_key("k2", 2), wallet_key("k1", 1)];
+
+        // -- Plaintext row.
+        let sealed = reencrypt_with(&keys, "ab cd").unwrap().unwrap();
+        assert!(sealed.starts_with("#e1#k2#"));
+        assert_eq!(decrypt_with(&keys, &sealed).unwrap(), "ab cd");
+
+        // -- Row sealed by the retired key.
+        let old = encrypt_with(&old_keys[0], "ab cd").unwrap();
+        let rotated = reencrypt_with(&keys, &old).unwrap().unwrap();
+        assert!(rotated.starts_with("#e1#k2#"));
+        assert_eq!(decrypt_with(&keys, &rotated).unwrap(), "ab cd");
+
+        // -- Already on the active key.
+        assert!(reencrypt_with(&keys, &rotated).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_decrypt_unknown_key_fail() {
+        let envelope = encrypt_with(&wallet_key("k1", 1), "ab cd").unwrap();
+
+        let res = decrypt_with(&[wallet_key("k2", 2)], &envelope);
+
+        assert!(matches!(res, Err(Error::KeyNotFound(id)) if id == "k1"));
+    }
+}
+// endregion:    --- Tests
@@ -9,22 +9,44 @@ pub enum Error {
     CtxCannotNewRootCtx,
 }
 
+/// Role of a user, the `role` column of the user table.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum Role {
+    Player,
+    Admin,
+}
+
+impl Role {
+    /// Unknown roles get the least privileges.
+    pub fn from_db(role: &str) -> Self {
+        match role {
+            "admin" => Role::Admin,
+            _ => Role::Player,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Ctx {
     user_id: i64,
+    role: Role,
 }
 
 // Constructors.
 impl Ctx {
+    /// The server itself, bypasses the access checks of the model layer.
     pub fn root_ctx() -> Self {
-        Ctx { user_id: 0 }
+        Ctx {
+            user_id: 0,
+            role: Role::Admin,
+        }
     }
 
-    pub fn new(user_id: i64) -> Result<Self> {
+    pub fn new(user_id: i64, role: Role) -> Result<Self> {
         if user_id == 0 {
             Err(Error::CtxCannotNewRootCtx)
         } else {
-            Ok(Self { user_id })
+            Ok(Self { user_id, role })
         }
     }
 }
@@ -34,6 +56,14 @@ impl Ctx {
     pub fn user_id(&self) -> i64 {
         self.user_id
     }
+
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.user_id == 0
+    }
 }
 
 // region:    --- Error Boilerplate
@@ -1,7 +1,8 @@
 use sui_sdk::error::Error as SuiError;
 use sui_sdk::types::base_types::ObjectIDParseError;
 
-use crate::models;
+use crate::sui_call::call_api;
+use crate::{indexer, models};
 
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -14,12 +15,22 @@ pub enum Error {
 
     Model(models::Error),
 
+    Indexer(indexer::Error),
+
     ObjectID(ObjectIDParseError),
 
     Sui(SuiError),
+    SuiCall(call_api::Error),
+    Keystore(String),
 }
 
 // region:    --- Froms
+impl From<indexer::Error> for Error {
+    fn from(val: indexer::Error) -> Self {
+        Self::Indexer(val)
+    }
+}
+
 impl From<models::Error> for Error {
     fn from(val: models::Error) -> Self {
         Self::Model(val)
@@ -37,6 +48,12 @@ impl From<SuiError> for Error {
         Self::Sui(val)
     }
 }
+
+impl From<call_api::Error> for Error {
+    fn from(val: call_api::Error) -> Self {
+        Self::SuiCall(val)
+    }
+}
 // endregion: --- Froms
 
 // region:    --- Error Boilerplate
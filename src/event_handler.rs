@@ -2,36 +2,36 @@ use anyhow;
 use dotenvy::dotenv;
 use serenity::async_trait;
 use serenity::builder::{CreateApplicationCommand, CreateApplicationCommands};
-use serenity::futures::StreamExt;
 use serenity::model::application::command::Command;
-use serenity::model::application::interaction::application_command::CommandDataOption;
+use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
+use serenity::model::application::interaction::autocomplete::AutocompleteInteraction;
 use serenity::model::application::interaction::{Interaction, InteractionResponseType};
 use serenity::model::channel::Message;
 use serenity::model::gateway::Ready;
 use serenity::model::id::{ApplicationId, ChannelId, GuildId, UserId};
 use serenity::prelude::*;
 use std::env;
-use std::str::FromStr;
-use sui_keys::keystore::Keystore;
 use sui_sdk::types::base_types::{ObjectID, SuiAddress};
-use sui_sdk::{SuiClient, SuiClientBuilder};
+use sui_sdk::SuiClientBuilder;
 use tracing::{debug, info};
 
-use crate::commands;
+use uuid::Uuid;
+
+use crate::commands::{ClientError, CommandRegistry, CommandResponse, Error, Result};
 use crate::config::Config;
 use crate::ctx::Ctx;
-use crate::game_state::UserGameState;
-use crate::models::discord_profile::{DiscordProfile, DiscordProfileBmc};
+use crate::log::log_command;
 use crate::models::user::UserInfo;
-use crate::models::{ModelManager, UserBmc};
+use crate::models::{self, ModelManager, UserBmc};
+use crate::sui_call::call_api::SuiExecutor;
 
 pub struct Handler {
-    pub sui_client: SuiClient,
     pub package_id: ObjectID,
     pub default_address: SuiAddress,
     pub mm: ModelManager,
     pub config: &'static Config,
-    pub keystore: Keystore,
+    pub executor: SuiExecutor,
+    pub commands: CommandRegistry,
 }
 
 #[async_trait]
@@ -40,7 +40,7 @@ impl EventHandler for Handler {
         match msg.content.as_str() {
             "hello" => {
                 if let Err(why) = msg.channel_id.say(&ctx.http, "world").await {
-                    println!("Error sending message: {:?}", why);
+                    debug!("Error sending message: {:?}", why);
                 }
             }
             _ => (),
@@ -48,148 +48,216 @@ impl EventHandler for Handler {
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        if let Interaction::ApplicationCommand(command) = interaction {
-            let mut res = "".to_string();
-
-            // TODO: add command -> function
-            if (is_player(&self.mm, i64::from(command.user.id)).await) {
-                let user_info = UserBmc::get_user_info_by_discord_id(
-                    &Ctx::root_ctx(),
-                    &self.mm,
-                    command.user.id.into(),
-                )
-                .await
-                .expect("something wrong when get user info");
+        match interaction {
+            Interaction::ApplicationCommand(command) => self.run_command(&ctx, command).await,
+            Interaction::Autocomplete(autocomplete) => self.autocomplete(&ctx, autocomplete).await,
+            _ => (),
+        }
+    }
 
-                match command.data.name.as_str() {
-                    "state" => {
-                        res = get_game_state(&self, &user_info).await;
-                    }
-                    // "hunt" => {
-                    //     res = do_hunt(&self, &command.data.options, &user_info).await;
-                    // }
-                    "battle" => {
-                        if check_sui(&self.sui_client, &user_info.wallet.pub_key).await {
-                            res = do_battle(&self, &command.data.options, &user_info).await;
-                        } else {
-                            res = "you have no SUI coin".into();
-                        }
-                    }
-                    _ => res = "Player already exist".to_string(),
-                };
-            } else {
-                match command.data.name.as_str() {
-                    "register" => {
-                        let user_id = command.user.id; // 530364905812131840
-                        let r_uri = self
-                            .config
-                            .CLOUDFLARE_SERVER_URL
-                            .clone()
-                            .strip_prefix("https://")
-                            .unwrap_or_default()
-                            .to_string();
-
-                        res = format!("Enter this link to authorize and register: https://discord.com/api/oauth2/authorize?client_id=1172504182691991562&redirect_uri=https%3A%2F%2F{}%2Fauth%2Fregister&response_type=code&scope=identify", r_uri);
-                        // res = "https://discord.com/api/oauth2/authorize?client_id=1172504182691991562&redirect_uri=http%3A%2F%2Flocalhost%3A8080%2Fauth%2Fregister&response_type=code&scope=identify".to_string();
-                    }
-                    _ => res = "Not a player".to_string(),
-                };
+    async fn ready(&self, ctx: Context, ready: Ready) {
+        info!("{} is connected!", ready.user.name);
+
+        let cs = Command::set_global_application_commands(ctx.http.clone(), |commands| {
+            for cmd in self.commands.iter() {
+                commands
+                    .create_application_command(|command| cmd.register(command.name(cmd.name())));
             }
+            commands
+        })
+        .await;
+
+        // info!("command: {cs:?}");
+    }
+}
+
+impl Handler {
+    async fn run_command(&self, ctx: &Context, command: ApplicationCommandInteraction) {
+        let Some(cmd) = self.commands.get(command.data.name.as_str()) else {
+            return self
+                .respond(ctx, &command, false, "Unknown command".into())
+                .await;
+        };
 
+        // -- Acknowledge right away, the chain call may exceed discord's 3 seconds window.
+        let deferred = cmd.defer();
+        if deferred {
             if let Err(why) = command
                 .create_interaction_response(&ctx.http, |response| {
-                    response
-                        .kind(InteractionResponseType::ChannelMessageWithSource)
-                        .interaction_response_data(|message| message.content(res))
+                    response.kind(InteractionResponseType::DeferredChannelMessageWithSource)
                 })
                 .await
             {
-                println!("Cannot respond to slash command: {}", why);
+                debug!("Cannot defer slash command: {:?}", why);
+                return;
+            }
+        }
+
+        let user_info = get_user_info(&self.mm, command.user.id).await;
+        let user_id = user_info
+            .as_ref()
+            .ok()
+            .and_then(|user_info| user_info.as_ref())
+            .map(|user_info| user_info.base_info.id);
+
+        let res = match user_info {
+            Ok(None) if cmd.require_player() => Err(Error::NotAPlayer),
+            Ok(user_info) => cmd.run(self, &command, user_info.as_ref()).await,
+            Err(e) => Err(e),
+        };
+
+        let uuid = Uuid::new_v4();
+        match res {
+            Ok(res) => {
+                let _ = log_command(uuid, cmd.name(), command.user.id, user_id, None, None).await;
+
+                self.respond(ctx, &command, deferred, res).await;
+            }
+            Err(e) => {
+                let client_error = e.client_error();
+                let _ = log_command(
+                    uuid,
+                    cmd.name(),
+                    command.user.id,
+                    user_id,
+                    Some(&e),
+                    Some(&client_error),
+                )
+                .await;
+
+                self.respond_error(ctx, &command, deferred, uuid, client_error)
+                    .await;
             }
         }
     }
 
-    async fn ready(&self, ctx: Context, ready: Ready) {
-        info!("{} is connected!", ready.user.name);
+    async fn respond(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        deferred: bool,
+        res: CommandResponse,
+    ) {
+        debug!("command {} response:\n{}", command.data.name, res.as_text());
+        let (content, embed) = res.into_message(command.guild_id.is_none());
 
-        let mut cmds2 = ApplicationId(self.config.APPLICATION_ID);
-        let cs = Command::set_global_application_commands(ctx.http.clone(), |commands| {
-            // commands.create_application_command(|command| commands::hunt::register(command));
-            commands.create_application_command(|command| commands::battle::register(command));
-            commands.create_application_command(|command| {
-                command.name("register").description("register to play...")
-            });
-            commands.create_application_command(|command| {
-                command.name("state").description("Get your game state.")
-            })
-        })
-        .await;
+        let result = if deferred {
+            command
+                .edit_original_interaction_response(&ctx.http, |message| {
+                    if let Some(content) = content {
+                        message.content(content);
+                    }
+                    if let Some(embed) = embed {
+                        message.set_embed(embed);
+                    }
+                    message
+                })
+                .await
+                .map(|_| ())
+        } else {
+            command
+                .create_interaction_response(&ctx.http, |response| {
+                    response
+                        .kind(InteractionResponseType::ChannelMessageWithSource)
+                        .interaction_response_data(|message| {
+                            if let Some(content) = content {
+                                message.content(content);
+                            }
+                            if let Some(embed) = embed {
+                                message.set_embed(embed);
+                            }
+                            message
+                        })
+                })
+                .await
+        };
 
-        // info!("command: {cs:?}");
+        if let Err(why) = result {
+            debug!("Cannot respond to slash command: {:?}", why);
+        }
     }
-}
 
-async fn is_player(mm: &ModelManager, discord_id: i64) -> bool {
-    let ctx = &Ctx::root_ctx();
-    DiscordProfileBmc::get_by_discord_id::<DiscordProfile>(ctx, mm, discord_id)
-        .await
-        .is_ok()
-}
+    /// Reply to the player with an ephemeral error message.
+    async fn respond_error(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        deferred: bool,
+        uuid: Uuid,
+        client_error: ClientError,
+    ) {
+        let content = format!("{} (request id: `{uuid}`)", client_error.message());
 
-async fn get_game_state(handler: &Handler, user_info: &UserInfo) -> String {
-    let a = UserGameState::new_state(
-        &handler.sui_client,
-        &handler.mm,
-        &handler.package_id,
-        user_info,
-    )
-    .await
-    .map_err(|e| debug!("error: {e:?}"))
-    .unwrap()
-    .get_game_state_board();
-    a
-}
+        let result = if deferred {
+            // The deferred response is public, replace it with an ephemeral followup.
+            if let Err(why) = command
+                .delete_original_interaction_response(&ctx.http)
+                .await
+            {
+                debug!("Cannot delete deferred response: {:?}", why);
+            }
 
-// async fn do_hunt(handler: &Handler, options: &[CommandDataOption], user_info: &UserInfo) -> String {
-//     let signer = get_signer(&user_info.wallet.pub_key);
-//     let _data = commands::hunt::do_hunt(&handler.sui_client, &handler.package_id, options, signer)
-//         .await
-//         .map_err(|e| println!("error: {e:?}"))
-//         .unwrap();
+            command
+                .create_followup_message(&ctx.http, |message| {
+                    message.content(content).ephemeral(true)
+                })
+                .await
+                .map(|_| ())
+        } else {
+            command
+                .create_interaction_response(&ctx.http, |response| {
+                    response
+                        .kind(InteractionResponseType::ChannelMessageWithSource)
+                        .interaction_response_data(|message| {
+                            message.content(content).ephemeral(true)
+                        })
+                })
+                .await
+        };
 
-//     get_game_state(&handler, user_info).await
-// }
+        if let Err(why) = result {
+            debug!("Cannot respond to slash command: {:?}", why);
+        }
+    }
 
-async fn check_sui(sui_client: &SuiClient, address: &String) -> bool {
-    let sui_coins_stream = sui_client.coin_read_api().get_coins_stream(
-        SuiAddress::from_str(address.clone().as_str()).unwrap(),
-        None,
-    );
+    async fn autocomplete(&self, ctx: &Context, autocomplete: AutocompleteInteraction) {
+        let Ok(Some(user_info)) = get_user_info(&self.mm, autocomplete.user.id).await else {
+            return;
+        };
+        let Some(cmd) = self.commands.get(autocomplete.data.name.as_str()) else {
+            return;
+        };
+        let Some(option) = autocomplete.data.options.iter().find(|o| o.focused) else {
+            return;
+        };
 
-    let sui_coin = sui_coins_stream.boxed().next().await;
+        let choices = cmd
+            .autocomplete(self, option, &user_info)
+            .await
+            .unwrap_or_else(|e| {
+                debug!("autocomplete {} error: {e:?}", cmd.name());
+                Vec::new()
+            });
 
-    sui_coin.is_some()
-}
-async fn do_battle(
-    handler: &Handler,
-    options: &[CommandDataOption],
-    user_info: &UserInfo,
-) -> String {
-    let signer = get_signer(&user_info.wallet.pub_key);
-    let _data = commands::battle::do_battle(
-        &handler.sui_client,
-        &handler.package_id,
-        // &handler.keystore,
-        options,
-        signer,
-    )
-    .await
-    .map_err(|e| println!("error: {e:?}"))
-    .unwrap();
-
-    get_game_state(&handler, user_info).await
+        if let Err(why) = autocomplete
+            .create_autocomplete_response(&ctx.http, |response| {
+                for (name, value) in choices {
+                    response.add_string_choice(name, value);
+                }
+                response
+            })
+            .await
+        {
+            debug!("Cannot respond to autocomplete: {:?}", why);
+        }
+    }
 }
 
-fn get_signer(pub_key: &str) -> SuiAddress {
-    SuiAddress::from_str(pub_key).unwrap_or_default()
+/// `None` when the discord user is not a registered player.
+async fn get_user_info(mm: &ModelManager, discord_id: UserId) -> Result<Option<UserInfo>> {
+    match UserBmc::get_user_info_by_discord_id(&Ctx::root_ctx(), mm, discord_id.into()).await {
+        Ok(user_info) => Ok(Some(user_info)),
+        Err(models::Error::EntityNotFound { .. }) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
 }
@@ -9,6 +9,7 @@ use crate::{
         ModelManager, UserBmc,
     },
     sui_call::{
+        gateway::SuiGateway,
         read_api::owned_objects::WePetGame,
         sui_move_object::{
             admin_obj::SuiAdminObject, bot_obj::SuiBotObject, hero_obj::SuiHeroObject,
@@ -18,12 +19,12 @@ use crate::{
     },
     utils::truncate_hex_string,
 };
-use futures::{future, StreamExt};
 use serde::Deserialize;
-use serenity::{futures, model::id::UserId};
+use serenity::{builder::CreateEmbed, model::id::UserId};
+use std::sync::Arc;
 use std::{env, str::FromStr};
 use sui_json_rpc_types::Coin;
-use sui_sdk::{types::base_types::SuiAddress, SuiClient};
+use sui_sdk::types::base_types::SuiAddress;
 use sui_types::base_types::ObjectID;
 
 #[derive(Debug, Deserialize)]
@@ -68,7 +69,7 @@ impl UserGameState {
     }
 
     pub async fn new_state(
-        sui_client: &SuiClient,
+        gateway: &Arc<dyn SuiGateway>,
         mm: &ModelManager,
         package: &ObjectID,
         user_info: &UserInfo,
@@ -78,15 +79,13 @@ impl UserGameState {
         let wallet = &user_info.wallet;
         let address = SuiAddress::from_str(wallet.pub_key.as_str()).ok().unwrap();
 
-        let wepet_game = WePetGame::new(sui_client.clone(), address, package.to_string().as_str());
+        let wepet_game = WePetGame::new(gateway.clone(), address, package.to_string().as_str());
 
-        let sui_coins_stream = sui_client.coin_read_api().get_coins_stream(address, None);
-
-        let sui_coin = sui_coins_stream
-            // .skip_while(|c| future::ready(c.balance < 5_000_000))
-            .boxed()
-            .next()
-            .await;
+        let sui_coin = gateway
+            .get_coins(address)
+            .await
+            .ok()
+            .and_then(|coins| coins.into_iter().next());
 
         let admin = wepet_game
             .get_sui_obj_first::<SuiAdminObject>(ADMIN_OBJECT_NAME)
@@ -179,4 +178,173 @@ impl UserGameState {
 
         game_state_board
     }
+
+    /// Rich version of `get_game_state_board` (the text board stays the DM/log fallback).
+    pub fn get_game_state_embed(&self) -> CreateEmbed {
+        let mut embed = CreateEmbed::default();
+
+        embed
+            .title(format!("{}'s game state", self.username))
+            .thumbnail(format!(
+                "https://cdn.discordapp.com/avatars/{}/{}.png",
+                self.id, self.avatar
+            ))
+            .colour(EMBED_COLOUR)
+            .description(format!("`{}`", self.address));
+
+        let balance = self
+            .sui_coin
+            .as_ref()
+            .map(|sui_coin| sui_coin.balance)
+            .unwrap_or_default();
+        embed.field("Balance", format_sui(balance), true);
+
+        if let Some(game_token) = &self.game_token {
+            embed.field("Game token", game_token.balance, true);
+        }
+
+        if let Some(hero) = &self.hero {
+            embed.field("Hero level", hero.level, true);
+        }
+
+        if let Some(pet) = &self.pet {
+            embed.field(
+                format!("Pet `{}`", truncate_hex_string(&pet.id, 6)),
+                format!(
+                    "Hp: {} {}\nExp: {}\nStrength: {}",
+                    hp_bar(pet.hp),
+                    pet.hp,
+                    pet.exp,
+                    pet.strength
+                ),
+                false,
+            );
+        }
+
+        if let Some(bot) = &self.bot {
+            embed.field(
+                format!("Bot `{}`", truncate_hex_string(&bot.id, 6)),
+                format!(
+                    "Hp: {} {}\nStrength: {}",
+                    hp_bar(bot.hp),
+                    bot.hp,
+                    bot.strength
+                ),
+                false,
+            );
+        }
+
+        if let Some(admin) = &self.admin {
+            embed.footer(|footer| {
+                footer.text(format!(
+                    "Admin {} - Total Bot: {}",
+                    truncate_hex_string(&admin.id, 6),
+                    admin.bot_animal_created
+                ))
+            });
+        }
+
+        embed
+    }
+}
+
+// region:    --- Embed Helpers
+const EMBED_COLOUR: u32 = 0x2ecc71;
+
+// Full hp of pets and bots, used to scale the hp bars.
+const MAX_HP: u32 = 100;
+const HP_BAR_WIDTH: u32 = 10;
+
+fn hp_bar(hp: u32) -> String {
+    let filled = (hp.min(MAX_HP) * HP_BAR_WIDTH + MAX_HP - 1) / MAX_HP;
+
+    format!(
+        "{}{}",
+        "█".repeat(filled as usize),
+        "░".repeat((HP_BAR_WIDTH - filled) as usize)
+    )
+}
+
+fn format_sui(mist: u64) -> String {
+    format!("{:.4} SUI", mist as f64 / 1_000_000_000.0)
+}
+// endregion: --- Embed Helpers
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use dotenvy::dotenv;
+    use serial_test::serial;
+    use sui_types::base_types::SuiAddress;
+
+    use super::UserGameState;
+    use crate::_dev_init;
+    use crate::models::discord_profile::DiscordProfile;
+    use crate::models::user::UserInfo;
+    use crate::models::wallet::Wallet;
+    use crate::models::User;
+    use crate::sui_call::call_api::create_bot::create_bot;
+    use crate::sui_call::call_api::create_profile::create_profile;
+
+    const PLAYER: &str = "0xb6c599cba8061a60acc445217823251cc1f0c8b4259a4ec4c8f51be9a8e361aa";
+
+    fn user_info() -> UserInfo {
+        UserInfo {
+            base_info: User {
+                id: 1000,
+                username: Some("test_new_state".to_string()),
+                email: None,
+                role: "player".to_string(),
+            },
+            discord: DiscordProfile {
+                id: 1000,
+                discord_id: 42,
+                username: "test_new_state".to_string(),
+                global_name: "Test New State".to_string(),
+                avatar: "avatar".to_string(),
+            },
+            wallet: Wallet {
+                id: 1000,
+                pub_key: PLAYER.to_string(),
+                sign_type: "ed25519".to_string(),
+                last_faucet: None,
+            },
+        }
+    }
+
+    #[serial]
+    #[tokio::test]
+    async fn test_new_state_offline() {
+        dotenv().ok();
+
+        let mm = _dev_init::init_db_for_test().await;
+        let (sui, executor) = _dev_init::init_sui_for_test();
+        let player = SuiAddress::from_str(PLAYER).unwrap();
+
+        // -- Not registered on chain yet.
+        let state =
+            UserGameState::new_state(executor.gateway(), &mm, executor.package_id(), &user_info())
+                .await
+                .unwrap();
+        assert!(state.hero.is_none() && state.pet.is_none() && state.sui_coin.is_none());
+
+        create_profile(&executor, player).await.unwrap();
+        create_bot(&executor, player, 50, 5).await.unwrap();
+        sui.add_coin(player, 5_000_000);
+
+        let state =
+            UserGameState::new_state(executor.gateway(), &mm, executor.package_id(), &user_info())
+                .await
+                .unwrap();
+
+        assert_eq!(state.hero.unwrap().level, 1);
+        assert_eq!(state.pet.unwrap().hp, 100);
+        assert_eq!(state.bot.unwrap().strength, 5);
+        assert_eq!(state.sui_coin.unwrap().balance, 5_000_000);
+        // -- The admin object stays with the game admin.
+        assert!(state.admin.is_none());
+    }
 }
+// endregion: --- Tests
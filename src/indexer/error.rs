@@ -0,0 +1,41 @@
+use serde::Serialize;
+
+use crate::models;
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, Serialize, strum_macros::AsRefStr)]
+#[serde(tag = "type", content = "data")]
+pub enum Error {
+    BadCursor(String),
+    BadModule(String),
+    EventParse { event_type: String, reason: String },
+    SuiRpc(String),
+
+    // -- Modules
+    Model(models::Error),
+}
+
+// region:    --- Froms
+impl From<models::Error> for Error {
+    fn from(val: models::Error) -> Self {
+        Self::Model(val)
+    }
+}
+
+impl From<sui_sdk::error::Error> for Error {
+    fn from(val: sui_sdk::error::Error) -> Self {
+        Self::SuiRpc(val.to_string())
+    }
+}
+// endregion: --- Froms
+
+// region:    --- Error Boilerplate
+impl core::fmt::Display for Error {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        write!(fmt, "{self:?}")
+    }
+}
+
+impl std::error::Error for Error {}
+// endregion:    --- Error Boilerplate
@@ -0,0 +1,117 @@
+use serde::Deserialize;
+use serde_json::Value;
+use serde_with::{serde_as, DisplayFromStr, PickFirst};
+
+use super::error::{Error, Result};
+
+// Names of the event structs emitted by the `we_pet_game` module.
+pub const BATTLE_RESULT_EVENT: &str = "BattleResult";
+pub const PET_LEVEL_UP_EVENT: &str = "PetLevelUp";
+pub const BOT_CREATED_EVENT: &str = "BotCreated";
+
+// region:    --- Game Events
+
+/// `we_pet_game` event payloads, as found in the `parsed_json` of a sui event.
+/// Move `u64` values are sent as strings, smaller integers as numbers.
+#[derive(Debug, PartialEq)]
+pub enum GameEvent {
+    BattleResult(BattleResult),
+    PetLevelUp(PetLevelUp),
+    BotCreated(BotCreated),
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct BattleResult {
+    pub pet_id: String,
+    pub bot_id: String,
+    pub pet_won: bool,
+}
+
+#[serde_as]
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct PetLevelUp {
+    pub pet_id: String,
+    #[serde_as(as = "PickFirst<(_, DisplayFromStr)>")]
+    pub level: u64,
+}
+
+#[serde_as]
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct BotCreated {
+    pub bot_id: String,
+    pub owner: String,
+    #[serde_as(as = "PickFirst<(_, DisplayFromStr)>")]
+    pub hp: u64,
+    #[serde_as(as = "PickFirst<(_, DisplayFromStr)>")]
+    pub strength: u64,
+}
+
+impl GameEvent {
+    /// Parse the event struct `name`, `None` for events the indexer does not keep.
+    pub fn parse(name: &str, json: &Value) -> Result<Option<Self>> {
+        let event = match name {
+            BATTLE_RESULT_EVENT => GameEvent::BattleResult(from_json(name, json)?),
+            PET_LEVEL_UP_EVENT => GameEvent::PetLevelUp(from_json(name, json)?),
+            BOT_CREATED_EVENT => GameEvent::BotCreated(from_json(name, json)?),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(event))
+    }
+}
+
+fn from_json<T: for<'de> Deserialize<'de>>(name: &str, json: &Value) -> Result<T> {
+    T::deserialize(json).map_err(|e| Error::EventParse {
+        event_type: name.to_string(),
+        reason: e.to_string(),
+    })
+}
+// endregion: --- Game Events
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{BotCreated, GameEvent, PetLevelUp};
+    use crate::indexer::Error;
+
+    #[test]
+    fn test_parse_game_events_ok() {
+        let level_up =
+            GameEvent::parse("PetLevelUp", &json!({ "pet_id": "0xabc", "level": "3" })).unwrap();
+        assert_eq!(
+            level_up,
+            Some(GameEvent::PetLevelUp(PetLevelUp {
+                pet_id: "0xabc".to_string(),
+                level: 3,
+            }))
+        );
+
+        let bot = GameEvent::parse(
+            "BotCreated",
+            &json!({ "bot_id": "0xb07", "owner": "0x1", "hp": 100, "strength": "8" }),
+        )
+        .unwrap();
+        assert_eq!(
+            bot,
+            Some(GameEvent::BotCreated(BotCreated {
+                bot_id: "0xb07".to_string(),
+                owner: "0x1".to_string(),
+                hp: 100,
+                strength: 8,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_game_events_unknown_and_bad() {
+        assert_eq!(GameEvent::parse("GameStarted", &json!({})).unwrap(), None);
+
+        let res = GameEvent::parse("BattleResult", &json!({ "pet_id": "0xabc" }));
+        assert!(
+            matches!(res, Err(Error::EventParse { event_type, .. }) if event_type == "BattleResult")
+        );
+    }
+}
+// endregion: --- Tests
@@ -0,0 +1,259 @@
+// region:    --- Modules
+
+mod error;
+pub mod events;
+
+pub use self::error::{Error, Result};
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use move_core_types::identifier::Identifier;
+use sui_json_rpc_types::{EventFilter, SuiEvent};
+use sui_sdk::SuiClient;
+use sui_types::base_types::ObjectID;
+use sui_types::digests::TransactionDigest;
+use sui_types::event::EventID;
+use tracing::{debug, error, info};
+
+use self::events::GameEvent;
+use crate::ctx::Ctx;
+use crate::models::game_event::{
+    BattleEvent, BotCreatedEvent, EventKey, GameEventBmc, IndexerCursorBmc, PetLevelUpEvent,
+};
+use crate::models::ModelManager;
+use crate::sui_call::MODULE_NAME;
+// endregion: --- Modules
+
+/// Cursor name of the `we_pet_game` event indexer in the `indexer_cursor` table.
+pub const INDEXER_NAME: &str = "we_pet_game_events";
+const EVENTS_PAGE_SIZE: usize = 50;
+
+/// Background task paging through the events of our package module and
+/// writing the game events to their tables.
+///
+/// The cursor is saved after each page. Rows are keyed by the sui event id,
+/// so a page indexed again after a restart (or a replay) is a no-op.
+pub struct Indexer {
+    sui_client: SuiClient,
+    package_id: ObjectID,
+    mm: ModelManager,
+    poll_interval: Duration,
+}
+
+impl Indexer {
+    pub fn new(
+        sui_client: SuiClient,
+        package_id: ObjectID,
+        mm: ModelManager,
+        poll_interval: Duration,
+    ) -> Self {
+        Indexer {
+            sui_client,
+            package_id,
+            mm,
+            poll_interval,
+        }
+    }
+
+    /// Index the events after `cursor` on the next run, `None` replays from the first event.
+    pub async fn replay_from(&self, cursor: Option<&EventID>) -> Result<()> {
+        let ctx = Ctx::root_ctx();
+
+        match cursor {
+            Some(cursor) => {
+                IndexerCursorBmc::save(&ctx, &self.mm, INDEXER_NAME, &event_key(cursor)).await?
+            }
+            None => IndexerCursorBmc::reset(&ctx, &self.mm, INDEXER_NAME).await?,
+        }
+
+        info!("{:<12} - replay from {cursor:?}", "INDEXER");
+
+        Ok(())
+    }
+
+    /// Poll for new events forever, errors are logged and retried on the next poll.
+    pub async fn run(self) {
+        loop {
+            match self.index_new_events().await {
+                Ok(0) => (),
+                Ok(count) => debug!("{:<12} - {count} game event(s) indexed", "INDEXER"),
+                Err(e) => error!("{:<12} - {e:?}", "INDEXER"),
+            }
+
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Index the pages after the stored cursor until caught up,
+    /// returns the number of new rows.
+    pub async fn index_new_events(&self) -> Result<usize> {
+        let ctx = Ctx::root_ctx();
+
+        let module = Identifier::new(MODULE_NAME).map_err(|e| Error::BadModule(e.to_string()))?;
+        let filter = EventFilter::MoveModule {
+            package: self.package_id,
+            module,
+        };
+
+        let mut cursor = IndexerCursorBmc::get(&ctx, &self.mm, INDEXER_NAME)
+            .await?
+            .map(|key| event_id(&key))
+            .transpose()?;
+
+        let mut count = 0;
+        loop {
+            let page = self
+                .sui_client
+                .event_api()
+                .query_events(filter.clone(), cursor, Some(EVENTS_PAGE_SIZE), false)
+                .await?;
+
+            for event in &page.data {
+                if self.index_event(&ctx, event).await? {
+                    count += 1;
+                }
+            }
+
+            if let Some(next_cursor) = page.next_cursor {
+                IndexerCursorBmc::save(&ctx, &self.mm, INDEXER_NAME, &event_key(&next_cursor))
+                    .await?;
+                cursor = Some(next_cursor);
+            }
+
+            if !page.has_next_page || page.data.is_empty() {
+                return Ok(count);
+            }
+        }
+    }
+
+    async fn index_event(&self, ctx: &Ctx, event: &SuiEvent) -> Result<bool> {
+        let name = event.type_.name.as_str();
+
+        // A malformed event is skipped, not retried forever.
+        // Fix the parser then replay from an earlier cursor.
+        let game_event = match GameEvent::parse(name, &event.parsed_json) {
+            Ok(Some(game_event)) => game_event,
+            Ok(None) => return Ok(false),
+            Err(e) => {
+                error!("{:<12} - skip event {:?}: {e:?}", "INDEXER", event.id);
+                return Ok(false);
+            }
+        };
+
+        let EventKey {
+            tx_digest,
+            event_seq,
+        } = event_key(&event.id);
+        let sender = event.sender.to_string();
+        let timestamp_ms = event.timestamp_ms.map(|ts| ts as i64);
+
+        let created = match game_event {
+            GameEvent::BattleResult(battle) => {
+                GameEventBmc::create_battle(
+                    ctx,
+                    &self.mm,
+                    BattleEvent {
+                        tx_digest,
+                        event_seq,
+                        sender,
+                        pet_id: battle.pet_id,
+                        bot_id: battle.bot_id,
+                        pet_won: battle.pet_won,
+                        timestamp_ms,
+                    },
+                )
+                .await?
+            }
+            GameEvent::PetLevelUp(level_up) => {
+                GameEventBmc::create_pet_level_up(
+                    ctx,
+                    &self.mm,
+                    PetLevelUpEvent {
+                        tx_digest,
+                        event_seq,
+                        sender,
+                        pet_id: level_up.pet_id,
+                        level: level_up.level as i64,
+                        timestamp_ms,
+                    },
+                )
+                .await?
+            }
+            GameEvent::BotCreated(bot) => {
+                GameEventBmc::create_bot_created(
+                    ctx,
+                    &self.mm,
+                    BotCreatedEvent {
+                        tx_digest,
+                        event_seq,
+                        sender,
+                        bot_id: bot.bot_id,
+                        owner: bot.owner,
+                        hp: bot.hp as i64,
+                        strength: bot.strength as i64,
+                        timestamp_ms,
+                    },
+                )
+                .await?
+            }
+        };
+
+        Ok(created)
+    }
+}
+
+// region:    --- Cursor
+
+/// Parse a cursor written as `{tx_digest}:{event_seq}`, see `--replay-from`.
+pub fn parse_cursor(cursor: &str) -> Result<EventID> {
+    let bad_cursor = || Error::BadCursor(cursor.to_string());
+
+    let (tx_digest, event_seq) = cursor.split_once(':').ok_or_else(bad_cursor)?;
+
+    Ok(EventID {
+        tx_digest: TransactionDigest::from_str(tx_digest).map_err(|_| bad_cursor())?,
+        event_seq: event_seq.parse().map_err(|_| bad_cursor())?,
+    })
+}
+
+fn event_key(id: &EventID) -> EventKey {
+    EventKey {
+        tx_digest: id.tx_digest.to_string(),
+        event_seq: id.event_seq as i64,
+    }
+}
+
+fn event_id(key: &EventKey) -> Result<EventID> {
+    parse_cursor(&format!("{}:{}", key.tx_digest, key.event_seq))
+}
+// endregion: --- Cursor
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use sui_types::digests::TransactionDigest;
+
+    use super::{event_id, event_key, parse_cursor, Error};
+
+    #[test]
+    fn test_parse_cursor_ok() {
+        let digest = TransactionDigest::random();
+
+        let cursor = parse_cursor(&format!("{digest}:7")).unwrap();
+
+        assert_eq!(cursor.tx_digest, digest);
+        assert_eq!(cursor.event_seq, 7);
+        assert_eq!(event_id(&event_key(&cursor)).unwrap(), cursor);
+    }
+
+    #[test]
+    fn test_parse_cursor_bad() {
+        assert!(matches!(parse_cursor("no-seq"), Err(Error::BadCursor(_))));
+        assert!(matches!(
+            parse_cursor(&format!("{}:x", TransactionDigest::random())),
+            Err(Error::BadCursor(_))
+        ));
+    }
+}
+// endregion: --- Tests
@@ -1,3 +1,4 @@
+use crate::commands;
 use crate::routes::rpc::RpcInfo;
 use crate::routes::Error;
 use crate::Result;
@@ -6,6 +7,7 @@ use axum::http::{Method, Uri};
 use serde::Serialize;
 use serde_json::{json, Value};
 use serde_with::skip_serializing_none;
+use serenity::model::id::UserId;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::debug;
 use uuid::Uuid;
@@ -55,6 +57,47 @@ pub async fn log_request(
     Ok(())
 }
 
+pub async fn log_command(
+    uuid: Uuid,
+    command: &str,
+    discord_id: UserId,
+    user_id: Option<i64>,
+    command_error: Option<&commands::Error>,
+    client_error: Option<&commands::ClientError>,
+) -> Result<()> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+
+    let error_type = command_error.map(|ce| ce.as_ref().to_string());
+    let error_data = serde_json::to_value(command_error)
+        .ok()
+        .and_then(|mut v| v.get_mut("data").map(|v| v.take()));
+
+    // Create the CommandLogLine
+    let log_line = CommandLogLine {
+        uuid: uuid.to_string(),
+        timestamp: timestamp.to_string(),
+
+        user_id,
+        discord_id: discord_id.0.to_string(),
+
+        command: command.to_string(),
+
+        client_error_type: client_error.map(|e| e.as_ref().to_string()),
+
+        error_type,
+        error_data,
+    };
+
+    debug!("COMMAND LOG LINE:\n{}", json!(log_line));
+
+    // TODO - Send to cloud-watch.
+
+    Ok(())
+}
+
 #[skip_serializing_none]
 #[derive(Serialize)]
 struct RequestLogLine {
@@ -77,3 +120,22 @@ struct RequestLogLine {
     error_type: Option<String>,
     error_data: Option<Value>,
 }
+
+#[skip_serializing_none]
+#[derive(Serialize)]
+struct CommandLogLine {
+    uuid: String,      // uuid string formatted
+    timestamp: String, // (should be iso8601)
+
+    // -- User and context attributes.
+    user_id: Option<i64>,
+    discord_id: String,
+
+    // -- discord command attributes.
+    command: String,
+
+    // -- Errors attributes.
+    client_error_type: Option<String>,
+    error_type: Option<String>,
+    error_data: Option<Value>,
+}
@@ -6,10 +6,12 @@
 mod _dev_init;
 mod commands;
 mod config;
+mod crypt;
 mod ctx;
 mod error;
 mod event_handler;
 mod game_state;
+mod indexer;
 mod log;
 mod middlewares;
 mod models;
@@ -19,19 +21,26 @@ mod store;
 mod sui_call;
 mod token;
 mod utils;
+mod worker;
 
 // re-exports
 pub use self::error::{Error, Result};
 pub use config::get_config;
 use dotenvy::dotenv;
-use sui_keys::keystore::{FileBasedKeystore, Keystore};
 use tokio::try_join;
 use tracing::field::debug;
 
+use crate::commands::CommandRegistry;
 use crate::event_handler::Handler;
+use crate::indexer::Indexer;
 // imports
+use crate::ctx::Ctx;
 use crate::middlewares::{mw_ctx_resolve::mw_ctx_resolve, mw_reponse_map::mw_reponse_map};
-use crate::routes::routes_static;
+use crate::models::wallet::WalletBmc;
+use crate::routes::{routes_static, AppState};
+use crate::sui_call::call_api::{GameKeystore, GasPool, SuiExecutor};
+use crate::sui_call::gateway::RpcGateway;
+use crate::worker::Worker;
 use anyhow;
 use axum::{middleware, Router};
 use models::ModelManager;
@@ -45,6 +54,8 @@ use std::env;
 use std::net::SocketAddr;
 use std::path::Path;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 use sui_sdk::types::base_types::{ObjectID, SuiAddress};
 use sui_sdk::{SuiClient, SuiClientBuilder};
 use tracing::{debug, info};
@@ -66,17 +77,76 @@ async fn main() -> Result<()> {
     // get app config
     let config = get_config();
 
-    // dev only
-    if config.ENVIRONMENT == "development" {
+    // dev only - wipe the database, the migrations below re-create the schema
+    if config.DB_RESET {
         _dev_init::init_db().await;
     }
 
-    // model - store layer (DB)
+    // model - store layer (DB), runs the pending migrations
     let mm = ModelManager::new().await?;
 
+    // seal the wallet phrases still in plaintext or on a rotated out key
+    let encrypted = WalletBmc::encrypt_phrases(&Ctx::root_ctx(), &mm).await?;
+    info!("{:<12} - {encrypted} wallet phrase(s) encrypted", "STARTUP");
+
+    // sui layer - shared move call executor
+    let sui_client = config.SUI_NETWORK.build_client().await?;
+    info!(
+        "{:<12} - sui network: {}",
+        "STARTUP", config.SUI_NETWORK.name
+    );
+    let package_id = ObjectID::from_str(config.PACKAGE.as_str())?;
+    let keystore = GameKeystore::from_config(config, mm.clone())
+        .map_err(|e| Error::Keystore(e.to_string()))?;
+    let gateway = RpcGateway::new(sui_client.clone(), config.SUI_NETWORK.clone());
+    let mut executor = SuiExecutor::new(Arc::new(gateway), package_id, keystore);
+
+    // gas pool - one admin gas coin per transaction in flight
+    if config.GAS_POOL_SIZE > 0 {
+        let gas_pool = Arc::new(GasPool::new(
+            SuiAddress::from_str(&config.SUI_CLIENT_ADDRESS)
+                .map_err(|_| Error::WrongFormat("SUI_CLIENT_ADDRESS"))?,
+            config.GAS_POOL_SIZE,
+            config.GAS_POOL_MIN_BALANCE,
+            Duration::from_secs(config.GAS_POOL_LEASE_TIMEOUT_SEC),
+        ));
+        executor = executor.with_gas_pool(gas_pool.clone());
+
+        // split the admin balance before the worker leases its first coin
+        gas_pool.rebalance(&executor).await?;
+        tokio::spawn(gas_pool.run(
+            executor.clone(),
+            Duration::from_secs(config.GAS_POOL_REBALANCE_SEC),
+        ));
+    }
+
+    // on-chain event indexer
+    let indexer = Indexer::new(
+        sui_client,
+        package_id,
+        mm.clone(),
+        Duration::from_millis(config.INDEXER_POLL_MS),
+    );
+    // one-shot, only the startups given `--replay-from` re-index
+    if let Some(replay_from) = replay_from_arg()? {
+        let cursor = match replay_from.as_str() {
+            "0" => None,
+            cursor => Some(indexer::parse_cursor(cursor)?),
+        };
+        indexer.replay_from(cursor.as_ref()).await?;
+    }
+    let indexer_task = tokio::spawn(indexer.run());
+
+    // outbox worker - executes the queued game actions
+    let worker_task =
+        tokio::spawn(Worker::new(mm.clone(), executor.clone()).run_many(config.OUTBOX_WORKERS));
+
     // route defination
     let routes = Router::new()
-        .merge(routes::routes(mm.clone()))
+        .merge(routes::routes(AppState {
+            mm: mm.clone(),
+            executor: executor.clone(),
+        }))
         .layer(middleware::map_response(mw_reponse_map))
         .layer(middleware::from_fn_with_state(mm.clone(), mw_ctx_resolve))
         .fallback_service(routes_static::serve_dir());
@@ -90,19 +160,15 @@ async fn main() -> Result<()> {
             | GatewayIntents::DIRECT_MESSAGES
             | GatewayIntents::MESSAGE_CONTENT;
 
-        let sui_client = SuiClientBuilder::default().build_devnet().await.unwrap();
         let default_address = SuiAddress::from_str(&config.SUI_CLIENT_ADDRESS).unwrap_or_default();
-        let keystore_path = Path::new("/home/ganzzi/.sui/sui_config/sui.keystore");
-        let mut keystore =
-            Keystore::from(FileBasedKeystore::new(&keystore_path.to_path_buf()).unwrap());
 
         let handler = Handler {
-            sui_client,
-            package_id: ObjectID::from_str(config.PACKAGE.as_str()).unwrap(),
+            package_id,
             default_address,
             config,
             mm: mm.clone(),
-            keystore,
+            executor,
+            commands: CommandRegistry::new(),
         };
 
         let mut discord_client = Client::builder(&discord_token, intents)
@@ -127,10 +193,30 @@ async fn main() -> Result<()> {
         info!("Axum server listening on: {}", addr);
     });
 
-    // Try to join both tasks concurrently
-    if let Err(e) = try_join!(discord_bot_task, axum_server_task) {
+    // Try to join the tasks concurrently
+    if let Err(e) = try_join!(
+        discord_bot_task,
+        axum_server_task,
+        indexer_task,
+        worker_task
+    ) {
         debug!("Error joining tasks: {:?}", e);
     }
 
     Ok(())
 }
+
+/// The cursor of `--replay-from {tx_digest}:{event_seq}` (`0` for every event).
+fn replay_from_arg() -> Result<Option<String>> {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--replay-from" {
+            return args
+                .next()
+                .map(Some)
+                .ok_or(Error::WrongFormat("--replay-from"));
+        }
+    }
+
+    Ok(None)
+}
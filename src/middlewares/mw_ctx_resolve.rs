@@ -1,7 +1,7 @@
 // region:    --- Imports
 use super::error::{CtxExtError, CtxExtResult};
 use crate::{
-    ctx::Ctx,
+    ctx::{Ctx, Role},
     models::{ModelManager, UserBmc, UserForAuth},
     routes::{Error, Result},
     token::{validate_token, Token},
@@ -57,7 +57,8 @@ async fn _ctx_resolve<B>(mm: &ModelManager, req: &Request<B>) -> CtxExtResult {
     // -- validate token
     validate_token(token, user.token_salt).map_err(|_| CtxExtError::FailValidate)?;
 
-    Ctx::new(user.id).map_err(|ex| CtxExtError::CtxCreateFail(ex.to_string()))
+    Ctx::new(user.id, Role::from_db(&user.role))
+        .map_err(|ex| CtxExtError::CtxCreateFail(ex.to_string()))
 }
 
 // region:    --- Ctx Extractor
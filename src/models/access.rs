@@ -0,0 +1,175 @@
+// region:    --- Imports
+use serde::Serialize;
+use tracing::{debug, warn};
+
+use crate::ctx::{Ctx, Role};
+use crate::models::error::{Error, Result};
+// endregion: --- Imports
+
+// region:    --- Types
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, strum_macros::AsRefStr)]
+pub enum Action {
+    Create,
+    Read,
+    List,
+    Update,
+    Delete,
+    /// A chain transaction.
+    Execute,
+    /// Read a secret in clear (e.g. a wallet phrase, to sign).
+    Decrypt,
+}
+
+/// A refused access, kept in `Error::AccessDenied`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AccessDenied {
+    pub entity: &'static str,
+    pub action: Action,
+    pub user_id: i64,
+}
+
+/// Who, besides the root ctx, may do an action on an entity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Rule {
+    Anyone,
+    /// The user owning the row, or an admin.
+    Owner,
+    Admin,
+    /// Root ctx only (registration, chain sync, outbox processing).
+    System,
+}
+// endregion: --- Types
+
+// region:    --- Access Policy
+
+/// The access policy of every entity, by table name.
+/// Anything not listed here is left to the root ctx.
+fn rule(entity: &str, action: Action) -> Rule {
+    use Action::*;
+
+    match (entity, action) {
+        // -- A player reads their own account.
+        ("user" | "discord_profile" | "wallet", Read) => Rule::Owner,
+        ("user", Update | Delete) => Rule::Owner,
+        ("bot", Read | List) => Rule::Owner,
+        ("outbox", Read) => Rule::Owner,
+
+        // -- Game events are public chain data.
+        ("battle_event" | "pet_level_up_event" | "bot_created_event", Read | List) => Rule::Anyone,
+
+        // -- Admin screens.
+        ("user" | "discord_profile" | "wallet" | "audit_log", List) => Rule::Admin,
+
+        // -- Only the keystore reads a phrase in clear, not even an admin.
+        ("wallet", Decrypt) => Rule::System,
+
+        _ => Rule::System,
+    }
+}
+
+/// Check `ctx` may do `action` on the `entity` rows owned by `owner_id`
+/// (`None` when unknown, or when the rows have no owner).
+///
+/// Every bmc function calls this before touching the database, the
+/// decisions are logged here.
+pub(in crate::models) fn check(
+    ctx: &Ctx,
+    entity: &'static str,
+    action: Action,
+    owner_id: Option<i64>,
+) -> Result<()> {
+    if ctx.is_root() {
+        return Ok(());
+    }
+
+    let rule = rule(entity, action);
+    let allowed = match rule {
+        Rule::Anyone => true,
+        Rule::Owner => ctx.role() == Role::Admin || owner_id == Some(ctx.user_id()),
+        Rule::Admin => ctx.role() == Role::Admin,
+        Rule::System => false,
+    };
+
+    if allowed {
+        debug!(
+            "{:<12} - allow {:?} user {} {} {entity} owner {owner_id:?} ({rule:?})",
+            "ACCESS",
+            ctx.role(),
+            ctx.user_id(),
+            action.as_ref()
+        );
+        Ok(())
+    } else {
+        warn!(
+            "{:<12} - deny {:?} user {} {} {entity} owner {owner_id:?} ({rule:?})",
+            "ACCESS",
+            ctx.role(),
+            ctx.user_id(),
+            action.as_ref()
+        );
+        Err(Error::AccessDenied(AccessDenied {
+            entity,
+            action,
+            user_id: ctx.user_id(),
+        }))
+    }
+}
+// endregion: --- Access Policy
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::{check, AccessDenied, Action};
+    use crate::ctx::{Ctx, Role};
+    use crate::models::Error;
+
+    #[test]
+    fn test_check_owner_and_admin() {
+        let player = Ctx::new(1000, Role::Player).unwrap();
+        let admin = Ctx::new(1001, Role::Admin).unwrap();
+
+        // -- Own wallet only.
+        assert!(check(&player, "wallet", Action::Read, Some(1000)).is_ok());
+        assert!(matches!(
+            check(&player, "wallet", Action::Read, Some(1002)),
+            Err(Error::AccessDenied(denied)) if denied == AccessDenied {
+                entity: "wallet",
+                action: Action::Read,
+                user_id: 1000,
+            }
+        ));
+        assert!(check(&admin, "wallet", Action::Read, Some(1002)).is_ok());
+
+        // -- Listing every user.
+        assert!(check(&player, "user", Action::List, None).is_err());
+        assert!(check(&admin, "user", Action::List, None).is_ok());
+    }
+
+    #[test]
+    fn test_check_system_and_root() {
+        let admin = Ctx::new(1001, Role::Admin).unwrap();
+
+        assert!(check(&admin, "user", Action::Create, None).is_err());
+        assert!(check(&admin, "outbox", Action::Update, None).is_err());
+        assert!(check(&Ctx::root_ctx(), "outbox", Action::Update, None).is_ok());
+        assert!(check(&admin, "battle_event", Action::List, None).is_ok());
+    }
+
+    #[test]
+    fn test_check_decrypt_root_only() {
+        let player = Ctx::new(1000, Role::Player).unwrap();
+        let admin = Ctx::new(1001, Role::Admin).unwrap();
+
+        assert!(check(&player, "wallet", Action::Decrypt, Some(1000)).is_err());
+        assert!(matches!(
+            check(&admin, "wallet", Action::Decrypt, Some(1000)),
+            Err(Error::AccessDenied(denied)) if denied == AccessDenied {
+                entity: "wallet",
+                action: Action::Decrypt,
+                user_id: 1001,
+            }
+        ));
+        assert!(check(&Ctx::root_ctx(), "wallet", Action::Decrypt, Some(1000)).is_ok());
+    }
+}
+// endregion: --- Tests
@@ -0,0 +1,254 @@
+// region:    --- Imports
+use super::access::{self, Action};
+use super::base_crud::{self, DbBmc, FieldFilter, FilterOp, FilterValue, ListOptions};
+use super::ModelManager;
+use crate::ctx::Ctx;
+use crate::models::error::Result;
+use crate::utils::time::unix_timestamp;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use sqlb::Fields;
+use sqlx::{FromRow, PgConnection, Postgres};
+// endregion: --- Imports
+
+// Fields never written in clear in a diff.
+const SECRET_FIELDS: &[&str] = &["pwd", "phrase", "token_salt"];
+const REDACTED: &str = "[redacted]";
+
+// region:    --- Types
+#[derive(Clone, Fields, FromRow, Debug, Serialize)]
+pub struct AuditLog {
+    pub id: i64,
+    pub actor_id: i64,
+    pub user_id: Option<i64>,
+    pub entity: String,
+    pub entity_id: String,
+    pub action: String,
+    pub diff: Option<String>,
+    pub tx_digest: Option<String>,
+    pub created_at: i64,
+}
+
+#[derive(Fields)]
+struct AuditLogForCreate {
+    actor_id: i64,
+    user_id: Option<i64>,
+    entity: String,
+    entity_id: String,
+    action: String,
+    diff: Option<String>,
+    tx_digest: Option<String>,
+    created_at: i64,
+}
+// endregion: --- Types
+
+pub struct AuditLogBmc {}
+
+// region:    --- Audit Log Controller
+impl DbBmc for AuditLogBmc {
+    const TABLE: &'static str = "audit_log";
+}
+
+impl AuditLogBmc {
+    /// Record a row change, on the connection (or transaction) that made it.
+    ///
+    /// No access check, the change itself was checked.
+    #[allow(clippy::too_many_arguments)]
+    pub(in crate::models) async fn log_change(
+        conn: &mut PgConnection,
+        ctx: &Ctx,
+        entity: &'static str,
+        entity_id: String,
+        action: Action,
+        user_id: Option<i64>,
+        before: Option<Value>,
+        after: Option<Value>,
+    ) -> Result<()> {
+        let diff = diff(before.as_ref(), after.as_ref()).map(|diff| diff.to_string());
+
+        Self::insert(
+            conn,
+            AuditLogForCreate {
+                actor_id: ctx.user_id(),
+                user_id,
+                entity: entity.to_string(),
+                entity_id,
+                action: action.as_ref().to_string(),
+                diff,
+                tx_digest: None,
+                created_at: unix_timestamp(),
+            },
+        )
+        .await
+    }
+
+    /// Record a chain transaction made for the player `user_id`.
+    pub async fn log_tx(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        user_id: Option<i64>,
+        entity: &'static str,
+        entity_id: String,
+        tx_digest: String,
+    ) -> Result<()> {
+        access::check(ctx, Self::TABLE, Action::Create, user_id)?;
+
+        let mut conn = mm.db_conn().await?;
+
+        Self::insert(
+            &mut conn,
+            AuditLogForCreate {
+                actor_id: ctx.user_id(),
+                user_id,
+                entity: entity.to_string(),
+                entity_id,
+                action: Action::Execute.as_ref().to_string(),
+                diff: None,
+                tx_digest: Some(tx_digest),
+                created_at: unix_timestamp(),
+            },
+        )
+        .await
+    }
+
+    /// History of the player `user_id`, newest first unless `options` orders it.
+    pub async fn list_by_user(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        user_id: i64,
+        mut options: ListOptions,
+    ) -> Result<Vec<AuditLog>> {
+        options.filters.push(FieldFilter {
+            field: "user_id".to_string(),
+            op: FilterOp::Eq,
+            value: FilterValue::Int(user_id),
+        });
+        options.order_by.get_or_insert_with(|| "!id".to_string());
+
+        base_crud::list::<Self, AuditLog>(ctx, mm, options).await
+    }
+
+    async fn insert(conn: &mut PgConnection, data: AuditLogForCreate) -> Result<()> {
+        sqlb::insert()
+            .table(Self::TABLE)
+            .data(data.not_none_fields())
+            .exec(conn)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// The row `id_column = id` of `table` as json, for the before/after of a change.
+pub(in crate::models) async fn row_json<T>(
+    conn: &mut PgConnection,
+    table: &'static str,
+    id_column: &'static str,
+    id: T,
+) -> Result<Option<Value>>
+where
+    T: for<'q> sqlx::Encode<'q, Postgres> + sqlx::Type<Postgres> + Send,
+{
+    let row: Option<(String,)> = sqlx::query_as(&format!(
+        r#"SELECT to_jsonb(t)::text FROM "{table}" t WHERE {id_column} = $1"#
+    ))
+    .bind(id)
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.and_then(|(json,)| serde_json::from_str(&json).ok()))
+}
+// endregion: --- Audit Log Controller
+
+// region:    --- Diff
+
+/// The changed fields, `{field: {"before": .., "after": ..}}`, `None` when nothing changed.
+/// Secret fields only show that they changed.
+fn diff(before: Option<&Value>, after: Option<&Value>) -> Option<Value> {
+    let empty = Map::new();
+    let before = before.and_then(Value::as_object).unwrap_or(&empty);
+    let after = after.and_then(Value::as_object).unwrap_or(&empty);
+
+    let mut diff = Map::new();
+    for field in before.keys().chain(after.keys()) {
+        let (old, new) = (before.get(field), after.get(field));
+        if old == new || diff.contains_key(field) {
+            continue;
+        }
+
+        let redact = |value: Option<&Value>| match value {
+            Some(Value::Null) | None => Value::Null,
+            Some(_) if SECRET_FIELDS.contains(&field.as_str()) => REDACTED.into(),
+            Some(value) => value.clone(),
+        };
+
+        diff.insert(
+            field.clone(),
+            serde_json::json!({ "before": redact(old), "after": redact(new) }),
+        );
+    }
+
+    (!diff.is_empty()).then_some(Value::Object(diff))
+}
+// endregion: --- Diff
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use dotenvy::dotenv;
+    use serde_json::json;
+    use serial_test::serial;
+
+    use super::{diff, AuditLogBmc};
+    use crate::models::{ListOptions, UserBmc, UserForCreate};
+    use crate::{_dev_init, ctx::Ctx};
+
+    #[test]
+    fn test_diff_changed_fields_only() {
+        let before = json!({ "id": 1, "email": "a@b.c", "pwd": "hash_1" });
+        let after = json!({ "id": 1, "email": "x@b.c", "pwd": "hash_2" });
+
+        assert_eq!(
+            diff(Some(&before), Some(&after)),
+            Some(json!({
+                "email": { "before": "a@b.c", "after": "x@b.c" },
+                "pwd": { "before": "[redacted]", "after": "[redacted]" },
+            }))
+        );
+        assert_eq!(diff(Some(&before), Some(&before)), None);
+
+        // -- Created row.
+        assert_eq!(
+            diff(None, Some(&json!({ "id": 1 }))),
+            Some(json!({ "id": { "before": null, "after": 1 } }))
+        );
+    }
+
+    #[serial]
+    #[tokio::test]
+    async fn test_audit_log_create_delete() {
+        dotenv().ok();
+
+        let ctx = Ctx::root_ctx();
+        let mm = _dev_init::init_db_for_test().await;
+
+        let user_c = UserForCreate {
+            username: Some("test_audit_log".to_string()),
+            pwd: None,
+            email: None,
+        };
+        let id = UserBmc::create(&ctx, &mm, user_c).await.unwrap();
+        UserBmc::delete(&ctx, &mm, id).await.unwrap();
+
+        let logs = AuditLogBmc::list_by_user(&ctx, &mm, id, ListOptions::default())
+            .await
+            .unwrap();
+
+        let actions: Vec<_> = logs.iter().map(|log| log.action.as_str()).collect();
+        assert_eq!(actions, ["Delete", "Create"]);
+        assert_eq!(logs[1].entity, "user");
+        assert!(logs[1].diff.as_deref().unwrap().contains("test_audit_log"));
+        assert!(logs[1].diff.as_deref().unwrap().contains("[redacted]"));
+    }
+}
+// endregion: --- Tests
@@ -1,7 +1,10 @@
 use crate::ctx::Ctx;
+use crate::models::access::{self, Action};
+use crate::models::audit_log::{row_json, AuditLogBmc};
 use crate::models::error::{Error, Result};
 use crate::models::ModelManager;
-use sqlb::HasFields;
+use serde::Deserialize;
+use sqlb::{HasFields, SelectSqlBuilder};
 use sqlx::postgres::PgRow;
 use sqlx::FromRow;
 
@@ -9,40 +12,65 @@ use sqlx::FromRow;
 pub trait DbBmc {
     // table name in database
     const TABLE: &'static str;
+
+    /// The `id` of a row is the id of the user owning it (user, wallet, discord profile).
+    const ID_IS_OWNER: bool = false;
+}
+
+/// Owner of the row `id`, when the table tells it.
+fn owner_of<MC: DbBmc>(id: i64) -> Option<i64> {
+    MC::ID_IS_OWNER.then_some(id)
 }
 
 // region:    --- CRUD Functions - Base Controller
-pub async fn create<MC, E>(_ctx: &Ctx, mm: &ModelManager, data: E) -> Result<i64>
+pub async fn create<MC, E>(ctx: &Ctx, mm: &ModelManager, data: E) -> Result<i64>
 where
     MC: DbBmc,
     E: HasFields,
 {
-    let db_pool = mm.get_db_pool();
+    access::check(ctx, MC::TABLE, Action::Create, None)?;
+
+    let mut conn = mm.db_conn().await?;
 
     let fields = data.not_none_fields();
     let (id,) = sqlb::insert()
         .table(MC::TABLE)
         .data(fields)
         .returning(&["id"])
-        .fetch_one::<_, (i64,)>(db_pool)
+        .fetch_one::<_, (i64,)>(&mut *conn)
         .await?;
 
+    let after = row_json(&mut conn, MC::TABLE, "id", id).await?;
+    AuditLogBmc::log_change(
+        &mut conn,
+        ctx,
+        MC::TABLE,
+        id.to_string(),
+        Action::Create,
+        owner_of::<MC>(id),
+        None,
+        after,
+    )
+    .await?;
+
     Ok(id)
 }
 
-pub async fn get<MC, E>(_ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<E>
+pub async fn get<MC, E>(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<E>
 where
     MC: DbBmc,
     E: for<'r> FromRow<'r, PgRow> + Unpin + Send,
     E: HasFields,
 {
-    let db_pool = mm.get_db_pool();
+    access::check(ctx, MC::TABLE, Action::Read, owner_of::<MC>(id))?;
+
+    let mut conn = mm.db_conn().await?;
 
     let entity: E = sqlb::select()
         .table(MC::TABLE)
         .columns(E::field_names())
         .and_where("id", "=", id)
-        .fetch_optional(db_pool)
+        .fetch_optional(&mut *conn)
         .await?
         .ok_or(Error::EntityNotFound {
             entity: MC::TABLE,
@@ -52,68 +80,310 @@ where
     Ok(entity)
 }
 
-pub async fn list<MC, E>(_ctx: &Ctx, mm: &ModelManager) -> Result<Vec<E>>
+pub async fn list<MC, E>(ctx: &Ctx, mm: &ModelManager, options: ListOptions) -> Result<Vec<E>>
 where
     MC: DbBmc,
     E: for<'r> FromRow<'r, PgRow> + Unpin + Send,
     E: HasFields,
 {
-    let db_pool = mm.get_db_pool();
+    access::check(ctx, MC::TABLE, Action::List, None)?;
 
-    let entities: Vec<E> = sqlb::select()
-        .table(MC::TABLE)
-        .columns(E::field_names())
-        .order_by("id")
-        .fetch_all(db_pool)
-        .await?;
+    let field_names = E::field_names();
+
+    let mut sb = sqlb::select().table(MC::TABLE).columns(field_names);
+
+    for filter in &options.filters {
+        check_field(field_names, &filter.field)?;
+        sb = and_where_value(sb, &filter.field, filter.op.as_sql(), &filter.value);
+    }
+
+    let (order_field, desc) = options.order();
+    check_field(field_names, order_field)?;
+    if let Some(after) = &options.after {
+        sb = and_where_value(sb, order_field, if desc { "<" } else { ">" }, after);
+    }
+
+    let sb = sb
+        .order_by(options.order_by.as_deref().unwrap_or("id"))
+        .limit(options.limit())
+        .offset(options.offset.unwrap_or(0).max(0));
+
+    let mut conn = mm.db_conn().await?;
+    let entities: Vec<E> = sb.fetch_all(&mut *conn).await?;
 
     Ok(entities)
 }
 
-pub async fn update<MC, E>(_ctx: &Ctx, mm: &ModelManager, id: i64, data: E) -> Result<()>
+pub async fn update<MC, E>(ctx: &Ctx, mm: &ModelManager, id: i64, data: E) -> Result<()>
 where
     MC: DbBmc,
     E: HasFields,
 {
-    let db_pool = mm.get_db_pool();
+    access::check(ctx, MC::TABLE, Action::Update, owner_of::<MC>(id))?;
+
+    let mut conn = mm.db_conn().await?;
+
+    let before = row_json(&mut conn, MC::TABLE, "id", id).await?;
 
     let fields = data.not_none_fields();
     let count = sqlb::update()
         .table(MC::TABLE)
         .and_where("id", "=", id)
         .data(fields)
-        .exec(db_pool)
+        .exec(&mut *conn)
         .await?;
 
     if count == 0 {
-        Err(Error::EntityNotFound {
+        return Err(Error::EntityNotFound {
             entity: MC::TABLE,
             id,
-        })
-    } else {
-        Ok(())
+        });
     }
+
+    let after = row_json(&mut conn, MC::TABLE, "id", id).await?;
+    AuditLogBmc::log_change(
+        &mut conn,
+        ctx,
+        MC::TABLE,
+        id.to_string(),
+        Action::Update,
+        owner_of::<MC>(id),
+        before,
+        after,
+    )
+    .await
 }
 
-pub async fn delete<MC>(_ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()>
+pub async fn delete<MC>(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()>
 where
     MC: DbBmc,
 {
-    let db_pool = mm.get_db_pool();
+    access::check(ctx, MC::TABLE, Action::Delete, owner_of::<MC>(id))?;
+
+    let mut conn = mm.db_conn().await?;
+
+    let before = row_json(&mut conn, MC::TABLE, "id", id).await?;
 
     let count = sqlb::delete()
         .table(MC::TABLE)
         .and_where("id", "=", id)
-        .exec(db_pool)
+        .exec(&mut *conn)
         .await?;
 
     if count == 0 {
-        Err(Error::EntityNotFound {
+        return Err(Error::EntityNotFound {
             entity: MC::TABLE,
             id,
-        })
-    } else {
-        Ok(())
+        });
     }
+
+    AuditLogBmc::log_change(
+        &mut conn,
+        ctx,
+        MC::TABLE,
+        id.to_string(),
+        Action::Delete,
+        owner_of::<MC>(id),
+        before,
+        None,
+    )
+    .await
 }
 // endregion:    --- CRUD Functions - Base Controller
+
+// region:    --- List Options
+pub const LIST_LIMIT_DEFAULT: i64 = 100;
+pub const LIST_LIMIT_MAX: i64 = 1000;
+
+/// Filters, order and page of a `list` (e.g. the `list_*` rpc params).
+///
+/// Page with `offset`, or with `after` (keyset), the order field value of the
+/// last row of the previous page. Keyset paging needs a unique order field,
+/// `id` by default.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ListOptions {
+    #[serde(default)]
+    pub filters: Vec<FieldFilter>,
+    /// Field to order by, `!field` for descending.
+    pub order_by: Option<String>,
+    /// Defaults to `LIST_LIMIT_DEFAULT`, at most `LIST_LIMIT_MAX`.
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    pub after: Option<FilterValue>,
+}
+
+impl ListOptions {
+    /// Order field and whether it is descending.
+    fn order(&self) -> (&str, bool) {
+        match self.order_by.as_deref() {
+            Some(order_by) => match order_by.strip_prefix('!') {
+                Some(field) => (field, true),
+                None => (order_by, false),
+            },
+            None => ("id", false),
+        }
+    }
+
+    fn limit(&self) -> i64 {
+        self.limit
+            .unwrap_or(LIST_LIMIT_DEFAULT)
+            .clamp(1, LIST_LIMIT_MAX)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldFilter {
+    pub field: String,
+    pub op: FilterOp,
+    pub value: FilterValue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterOp {
+    Eq,
+    NotEq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    Like,
+    #[serde(rename = "ilike")]
+    ILike,
+}
+
+impl FilterOp {
+    fn as_sql(self) -> &'static str {
+        match self {
+            FilterOp::Eq => "=",
+            FilterOp::NotEq => "!=",
+            FilterOp::Lt => "<",
+            FilterOp::Lte => "<=",
+            FilterOp::Gt => ">",
+            FilterOp::Gte => ">=",
+            FilterOp::Like => "LIKE",
+            FilterOp::ILike => "ILIKE",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum FilterValue {
+    Bool(bool),
+    Int(i64),
+    Text(String),
+}
+
+/// Only the fields of the listed entity can be filtered or ordered by,
+/// they are the only names written in the sql.
+fn check_field(field_names: &[&str], field: &str) -> Result<()> {
+    if field_names.contains(&field) {
+        Ok(())
+    } else {
+        Err(Error::ListFieldUnknown {
+            field: field.to_string(),
+        })
+    }
+}
+
+fn and_where_value<'a>(
+    sb: SelectSqlBuilder<'a>,
+    field: &str,
+    op: &'static str,
+    value: &FilterValue,
+) -> SelectSqlBuilder<'a> {
+    match value.clone() {
+        FilterValue::Bool(value) => sb.and_where(field, op, value),
+        FilterValue::Int(value) => sb.and_where(field, op, value),
+        FilterValue::Text(value) => sb.and_where(field, op, value),
+    }
+}
+// endregion: --- List Options
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use dotenvy::dotenv;
+    use serde_json::json;
+    use serial_test::serial;
+
+    use super::{check_field, FilterOp, FilterValue, ListOptions, LIST_LIMIT_MAX};
+    use crate::models::{Error, User, UserBmc, UserForCreate};
+    use crate::{_dev_init, ctx::Ctx};
+
+    #[test]
+    fn test_list_options_from_json() {
+        let options: ListOptions = serde_json::from_value(json!({
+            "filters": [
+                { "field": "username", "op": "ilike", "value": "%bo%" },
+                { "field": "id", "op": "gte", "value": 1000 }
+            ],
+            "order_by": "!id",
+            "limit": 5000,
+            "after": 2000
+        }))
+        .unwrap();
+
+        assert_eq!(options.filters[0].op, FilterOp::ILike);
+        assert_eq!(options.filters[1].value, FilterValue::Int(1000));
+        assert_eq!(options.order(), ("id", true));
+        assert_eq!(options.limit(), LIST_LIMIT_MAX);
+        assert_eq!(options.after, Some(FilterValue::Int(2000)));
+
+        let options = ListOptions::default();
+        assert_eq!(options.order(), ("id", false));
+    }
+
+    #[test]
+    fn test_list_options_unknown_field() {
+        let fields = &["id", "username"];
+
+        assert!(check_field(fields, "username").is_ok());
+        assert!(matches!(
+            check_field(fields, "pwd"),
+            Err(Error::ListFieldUnknown { field }) if field == "pwd"
+        ));
+    }
+
+    #[serial]
+    #[tokio::test]
+    async fn test_list_keyset_pages() {
+        dotenv().ok();
+
+        let ctx = Ctx::root_ctx();
+        let mm = _dev_init::init_db_for_test().await;
+
+        let mut ids = Vec::new();
+        for i in 0..3 {
+            let user_c = UserForCreate {
+                username: Some(format!("test_list_keyset_{i}")),
+                pwd: None,
+                email: None,
+            };
+            ids.push(UserBmc::create(&ctx, &mm, user_c).await.unwrap());
+        }
+
+        let page = |after: Option<i64>| {
+            serde_json::from_value::<ListOptions>(json!({
+                "filters": [{ "field": "username", "op": "like", "value": "test_list_keyset_%" }],
+                "limit": 2,
+                "after": after,
+            }))
+            .unwrap()
+        };
+
+        let first: Vec<User> = UserBmc::list(&ctx, &mm, page(None)).await.unwrap();
+        assert_eq!(first.iter().map(|u| u.id).collect::<Vec<_>>(), ids[..2]);
+
+        let second: Vec<User> = UserBmc::list(&ctx, &mm, page(Some(first[1].id)))
+            .await
+            .unwrap();
+        assert_eq!(second.iter().map(|u| u.id).collect::<Vec<_>>(), ids[2..]);
+
+        for id in ids {
+            UserBmc::delete(&ctx, &mm, id).await.unwrap();
+        }
+    }
+}
+// endregion: --- Tests
@@ -1,8 +1,10 @@
 // region:    --- Imports
+use super::audit_log::{row_json, AuditLogBmc};
 use super::base_crud::{update, DbBmc};
 use super::{base_crud, ModelManager};
 use crate::ctx::Ctx;
 use crate::get_config;
+use crate::models::access::{self, Action};
 use crate::models::error::{Error, Result};
 use crate::pwd::{self, ContentToHash};
 use serde::{Deserialize, Serialize};
@@ -45,13 +47,27 @@ impl BotBmc {
     where
         E: BotModel,
     {
-        let db_pool = mm.get_db_pool();
+        let mut conn = mm.db_conn().await?;
+
+        // -- The owner of the row, checked before reading it.
+        let owner: Option<Option<i64>> =
+            sqlx::query_scalar("SELECT user_id FROM bot WHERE bot_id = $1")
+                .bind(&bot_id)
+                .fetch_optional(&mut *conn)
+                .await?;
+        let Some(owner) = owner else {
+            return Err(Error::EntityNotFoundString {
+                entity: Self::TABLE,
+                id: bot_id,
+            });
+        };
+        access::check(ctx, Self::TABLE, Action::Read, owner)?;
 
         let entity: E = sqlb::select()
             .table(Self::TABLE)
             .columns(E::field_names())
             .and_where("bot_id", "=", bot_id.clone())
-            .fetch_optional(db_pool)
+            .fetch_optional(&mut *conn)
             .await?
             .ok_or(Error::EntityNotFoundString {
                 entity: Self::TABLE,
@@ -61,37 +77,90 @@ impl BotBmc {
         Ok(entity)
     }
 
+    pub async fn list_by_user<E>(ctx: &Ctx, mm: &ModelManager, user_id: i64) -> Result<Vec<E>>
+    where
+        E: BotModel,
+    {
+        access::check(ctx, Self::TABLE, Action::List, Some(user_id))?;
+
+        let mut conn = mm.db_conn().await?;
+
+        let entities: Vec<E> = sqlb::select()
+            .table(Self::TABLE)
+            .columns(E::field_names())
+            .and_where("user_id", "=", user_id)
+            .order_by("last_creation")
+            .fetch_all(&mut *conn)
+            .await?;
+
+        Ok(entities)
+    }
+
     pub async fn create(ctx: &Ctx, mm: &ModelManager, data: BotForCreate) -> Result<String> {
-        let db_pool = mm.get_db_pool();
+        access::check(ctx, Self::TABLE, Action::Create, Some(data.user_id))?;
+
+        let mut conn = mm.db_conn().await?;
 
+        let user_id = data.user_id;
         let fields = data.not_none_fields();
         let (id,) = sqlb::insert()
             .table(Self::TABLE)
             .data(fields)
             .returning(&["bot_id"])
-            .fetch_one::<_, (String,)>(db_pool)
+            .fetch_one::<_, (String,)>(&mut *conn)
             .await?;
 
+        let after = row_json(&mut conn, Self::TABLE, "bot_id", id.clone()).await?;
+        AuditLogBmc::log_change(
+            &mut conn,
+            ctx,
+            Self::TABLE,
+            id.clone(),
+            Action::Create,
+            Some(user_id),
+            None,
+            after,
+        )
+        .await?;
+
         Ok(id)
     }
 
     async fn delete(ctx: &Ctx, mm: &ModelManager, bot_id: String) -> Result<()> {
-        let db_pool = mm.get_db_pool();
+        access::check(ctx, Self::TABLE, Action::Delete, None)?;
+
+        let mut conn = mm.db_conn().await?;
+
+        let before = row_json(&mut conn, Self::TABLE, "bot_id", bot_id.clone()).await?;
+        let user_id = before
+            .as_ref()
+            .and_then(|bot| bot.get("user_id"))
+            .and_then(|user_id| user_id.as_i64());
 
         let count = sqlb::delete()
             .table(Self::TABLE)
             .and_where("bot_id", "=", bot_id.clone())
-            .exec(db_pool)
+            .exec(&mut *conn)
             .await?;
 
         if count == 0 {
-            Err(Error::EntityNotFoundString {
+            return Err(Error::EntityNotFoundString {
                 entity: Self::TABLE,
                 id: bot_id,
-            })
-        } else {
-            Ok(())
+            });
         }
+
+        AuditLogBmc::log_change(
+            &mut conn,
+            ctx,
+            Self::TABLE,
+            bot_id,
+            Action::Delete,
+            user_id,
+            before,
+            None,
+        )
+        .await
     }
 }
 // endregion:    --- Discord Profile Controller
@@ -101,8 +170,10 @@ impl BotBmc {
 mod tests {
     use crate::{
         _dev_init,
-        ctx::Ctx,
-        models::{bot::Bot, wallet::WalletForUpdateFaucet, ModelManager, UserBmc, UserForCreate},
+        ctx::{Ctx, Role},
+        models::{
+            bot::Bot, wallet::WalletForUpdateFaucet, Error, ModelManager, UserBmc, UserForCreate,
+        },
     };
     use dotenvy::dotenv;
     use serial_test::serial;
@@ -145,6 +216,17 @@ mod tests {
 
         assert_eq!(bot.last_creation, 10000012331);
 
+        // -- Checked against the owner of the row.
+        let owner = Ctx::new(user_id, Role::Player).unwrap();
+        assert!(BotBmc::get::<Bot>(&owner, &mm, bot_id.clone())
+            .await
+            .is_ok());
+        let other = Ctx::new(user_id + 1, Role::Player).unwrap();
+        assert!(matches!(
+            BotBmc::get::<Bot>(&other, &mm, bot_id.clone()).await,
+            Err(Error::AccessDenied(_))
+        ));
+
         BotBmc::delete(&ctx, &mm, bot_id).await.unwrap();
     }
 }
@@ -3,6 +3,7 @@ use super::base_crud::{update, DbBmc};
 use super::{base_crud, ModelManager};
 use crate::ctx::Ctx;
 use crate::get_config;
+use crate::models::access::{self, Action};
 use crate::models::error::{Error, Result};
 use crate::pwd::{self, ContentToHash};
 use serde::{Deserialize, Serialize};
@@ -42,6 +43,7 @@ pub struct DiscordProfileBmc {}
 // region:    --- Discord Profile Controller
 impl DbBmc for DiscordProfileBmc {
     const TABLE: &'static str = "discord_profile";
+    const ID_IS_OWNER: bool = true;
 }
 
 impl DiscordProfileBmc {
@@ -56,13 +58,24 @@ impl DiscordProfileBmc {
     where
         E: DiscordProfileModel,
     {
-        let db_pool = mm.get_db_pool();
+        let mut conn = mm.db_conn().await?;
+
+        // -- The owner of the row, checked before reading it.
+        let owner: i64 = sqlx::query_scalar("SELECT id FROM discord_profile WHERE discord_id = $1")
+            .bind(discord_id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or(Error::EntityNotFound {
+                entity: DiscordProfileBmc::TABLE,
+                id: discord_id,
+            })?;
+        access::check(ctx, Self::TABLE, Action::Read, Some(owner))?;
 
         let entity: E = sqlb::select()
             .table(DiscordProfileBmc::TABLE)
             .columns(E::field_names())
             .and_where("discord_id", "=", discord_id)
-            .fetch_optional(db_pool)
+            .fetch_optional(&mut *conn)
             .await?
             .ok_or(Error::EntityNotFound {
                 entity: DiscordProfileBmc::TABLE,
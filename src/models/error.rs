@@ -1,4 +1,5 @@
-use crate::{pwd, store};
+use crate::models::access::AccessDenied;
+use crate::{crypt, pwd, store};
 use serde::Serialize;
 use serde_with::{serde_as, DisplayFromStr};
 
@@ -9,8 +10,13 @@ pub type Result<T> = core::result::Result<T, Error>;
 pub enum Error {
     EntityNotFound { entity: &'static str, id: i64 },
     EntityNotFoundString { entity: &'static str, id: String },
+    UniqueViolation { constraint: Option<String> },
+    ListFieldUnknown { field: String },
+    AccessDenied(AccessDenied),
+    TxnDone,
 
     // -- Modules
+    Crypt(crypt::Error),
     Pwd(pwd::Error),
     Store(store::Error),
 
@@ -19,6 +25,12 @@ pub enum Error {
 }
 
 // region:    --- Froms
+impl From<crypt::Error> for Error {
+    fn from(val: crypt::Error) -> Self {
+        Self::Crypt(val)
+    }
+}
+
 impl From<pwd::Error> for Error {
     fn from(val: pwd::Error) -> Self {
         Self::Pwd(val)
@@ -33,7 +45,12 @@ impl From<store::Error> for Error {
 
 impl From<sqlx::Error> for Error {
     fn from(val: sqlx::Error) -> Self {
-        Self::Sqlx(val)
+        match val.as_database_error() {
+            Some(db_error) if db_error.is_unique_violation() => Self::UniqueViolation {
+                constraint: db_error.constraint().map(str::to_string),
+            },
+            _ => Self::Sqlx(val),
+        }
     }
 }
 // endregion: --- Froms
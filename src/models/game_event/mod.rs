@@ -0,0 +1,302 @@
+// region:    --- Imports
+use super::ModelManager;
+use crate::ctx::Ctx;
+use crate::models::access::{self, Action};
+use crate::models::error::Result;
+use serde::Serialize;
+use sqlb::Fields;
+use sqlx::FromRow;
+// endregion: --- Imports
+
+// region:    --- Types
+
+/// Sui event id, the primary key of every game event row.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct EventKey {
+    pub tx_digest: String,
+    pub event_seq: i64,
+}
+
+#[derive(Clone, Fields, FromRow, Debug, Serialize)]
+pub struct BattleEvent {
+    pub tx_digest: String,
+    pub event_seq: i64,
+    pub sender: String,
+    pub pet_id: String,
+    pub bot_id: String,
+    pub pet_won: bool,
+    pub timestamp_ms: Option<i64>,
+}
+
+#[derive(Clone, Fields, FromRow, Debug, Serialize)]
+pub struct PetLevelUpEvent {
+    pub tx_digest: String,
+    pub event_seq: i64,
+    pub sender: String,
+    pub pet_id: String,
+    pub level: i64,
+    pub timestamp_ms: Option<i64>,
+}
+
+#[derive(Clone, Fields, FromRow, Debug, Serialize)]
+pub struct BotCreatedEvent {
+    pub tx_digest: String,
+    pub event_seq: i64,
+    pub sender: String,
+    pub bot_id: String,
+    pub owner: String,
+    pub hp: i64,
+    pub strength: i64,
+    pub timestamp_ms: Option<i64>,
+}
+// endregion: --- Types
+
+pub struct GameEventBmc {}
+
+// region:    --- Game Event Controller
+impl GameEventBmc {
+    /// Insert a battle result, returns false when the event was already indexed.
+    pub async fn create_battle(ctx: &Ctx, mm: &ModelManager, data: BattleEvent) -> Result<bool> {
+        access::check(ctx, "battle_event", Action::Create, None)?;
+
+        let mut conn = mm.db_conn().await?;
+
+        let count = sqlx::query(
+            "INSERT INTO battle_event (tx_digest, event_seq, sender, pet_id, bot_id, pet_won, timestamp_ms)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             ON CONFLICT (tx_digest, event_seq) DO NOTHING",
+        )
+        .bind(data.tx_digest)
+        .bind(data.event_seq)
+        .bind(data.sender)
+        .bind(data.pet_id)
+        .bind(data.bot_id)
+        .bind(data.pet_won)
+        .bind(data.timestamp_ms)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+        Ok(count > 0)
+    }
+
+    pub async fn create_pet_level_up(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        data: PetLevelUpEvent,
+    ) -> Result<bool> {
+        access::check(ctx, "pet_level_up_event", Action::Create, None)?;
+
+        let mut conn = mm.db_conn().await?;
+
+        let count = sqlx::query(
+            "INSERT INTO pet_level_up_event (tx_digest, event_seq, sender, pet_id, level, timestamp_ms)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (tx_digest, event_seq) DO NOTHING",
+        )
+        .bind(data.tx_digest)
+        .bind(data.event_seq)
+        .bind(data.sender)
+        .bind(data.pet_id)
+        .bind(data.level)
+        .bind(data.timestamp_ms)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+        Ok(count > 0)
+    }
+
+    pub async fn create_bot_created(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        data: BotCreatedEvent,
+    ) -> Result<bool> {
+        access::check(ctx, "bot_created_event", Action::Create, None)?;
+
+        let mut conn = mm.db_conn().await?;
+
+        let count = sqlx::query(
+            "INSERT INTO bot_created_event (tx_digest, event_seq, sender, bot_id, owner, hp, strength, timestamp_ms)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             ON CONFLICT (tx_digest, event_seq) DO NOTHING",
+        )
+        .bind(data.tx_digest)
+        .bind(data.event_seq)
+        .bind(data.sender)
+        .bind(data.bot_id)
+        .bind(data.owner)
+        .bind(data.hp)
+        .bind(data.strength)
+        .bind(data.timestamp_ms)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+        Ok(count > 0)
+    }
+
+    /// Battles a pet fought, oldest first.
+    pub async fn list_battles_by_pet(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        pet_id: &str,
+    ) -> Result<Vec<BattleEvent>> {
+        access::check(ctx, "battle_event", Action::List, None)?;
+
+        let mut conn = mm.db_conn().await?;
+
+        let battles = sqlb::select()
+            .table("battle_event")
+            .columns(BattleEvent::field_names())
+            .and_where("pet_id", "=", pet_id.to_string())
+            .order_by("timestamp_ms")
+            .fetch_all(&mut *conn)
+            .await?;
+
+        Ok(battles)
+    }
+}
+// endregion: --- Game Event Controller
+
+// region:    --- Indexer Cursor
+
+#[derive(Clone, Fields, FromRow, Debug, Serialize)]
+pub struct IndexerCursor {
+    pub name: String,
+    pub tx_digest: String,
+    pub event_seq: i64,
+    pub updated_at: i64,
+}
+
+pub struct IndexerCursorBmc {}
+
+impl IndexerCursorBmc {
+    pub async fn get(ctx: &Ctx, mm: &ModelManager, name: &str) -> Result<Option<EventKey>> {
+        access::check(ctx, "indexer_cursor", Action::Read, None)?;
+
+        let mut conn = mm.db_conn().await?;
+
+        let cursor: Option<IndexerCursor> = sqlb::select()
+            .table("indexer_cursor")
+            .columns(IndexerCursor::field_names())
+            .and_where("name", "=", name.to_string())
+            .fetch_optional(&mut *conn)
+            .await?;
+
+        Ok(cursor.map(|c| EventKey {
+            tx_digest: c.tx_digest,
+            event_seq: c.event_seq,
+        }))
+    }
+
+    /// Store the last indexed event of the indexer `name`.
+    pub async fn save(ctx: &Ctx, mm: &ModelManager, name: &str, key: &EventKey) -> Result<()> {
+        access::check(ctx, "indexer_cursor", Action::Update, None)?;
+
+        let mut conn = mm.db_conn().await?;
+
+        sqlx::query(
+            "INSERT INTO indexer_cursor (name, tx_digest, event_seq, updated_at)
+             VALUES ($1, $2, $3, (extract(epoch from now()) * 1000)::BIGINT)
+             ON CONFLICT (name) DO UPDATE
+             SET tx_digest = EXCLUDED.tx_digest,
+                 event_seq = EXCLUDED.event_seq,
+                 updated_at = EXCLUDED.updated_at",
+        )
+        .bind(name)
+        .bind(&key.tx_digest)
+        .bind(key.event_seq)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Forget the cursor, the indexer starts over from the first event.
+    pub async fn reset(ctx: &Ctx, mm: &ModelManager, name: &str) -> Result<()> {
+        access::check(ctx, "indexer_cursor", Action::Delete, None)?;
+
+        let mut conn = mm.db_conn().await?;
+
+        sqlx::query("DELETE FROM indexer_cursor WHERE name = $1")
+            .bind(name)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+}
+// endregion: --- Indexer Cursor
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use dotenvy::dotenv;
+    use serial_test::serial;
+
+    use super::{BattleEvent, EventKey, GameEventBmc, IndexerCursorBmc};
+    use crate::{_dev_init, ctx::Ctx};
+
+    #[serial]
+    #[tokio::test]
+    async fn test_battle_event_idempotent() {
+        dotenv().ok();
+
+        let ctx = Ctx::root_ctx();
+        let mm = _dev_init::init_db_for_test().await;
+
+        let battle = BattleEvent {
+            tx_digest: "test_battle_event_idempotent".to_string(),
+            event_seq: 0,
+            sender: "0x1".to_string(),
+            pet_id: "0xpet_idempotent".to_string(),
+            bot_id: "0xbot".to_string(),
+            pet_won: true,
+            timestamp_ms: Some(1),
+        };
+
+        assert!(GameEventBmc::create_battle(&ctx, &mm, battle.clone())
+            .await
+            .unwrap());
+        // -- Replayed event.
+        assert!(!GameEventBmc::create_battle(&ctx, &mm, battle)
+            .await
+            .unwrap());
+
+        let battles = GameEventBmc::list_battles_by_pet(&ctx, &mm, "0xpet_idempotent")
+            .await
+            .unwrap();
+        assert_eq!(battles.len(), 1);
+    }
+
+    #[serial]
+    #[tokio::test]
+    async fn test_indexer_cursor_save_get_reset() {
+        dotenv().ok();
+
+        let ctx = Ctx::root_ctx();
+        let mm = _dev_init::init_db_for_test().await;
+        let name = "test_indexer_cursor";
+
+        let key = EventKey {
+            tx_digest: "digest_1".to_string(),
+            event_seq: 3,
+        };
+        IndexerCursorBmc::save(&ctx, &mm, name, &key).await.unwrap();
+        let key = EventKey {
+            event_seq: 4,
+            ..key
+        };
+        IndexerCursorBmc::save(&ctx, &mm, name, &key).await.unwrap();
+
+        assert_eq!(
+            IndexerCursorBmc::get(&ctx, &mm, name).await.unwrap(),
+            Some(key)
+        );
+
+        IndexerCursorBmc::reset(&ctx, &mm, name).await.unwrap();
+        assert_eq!(IndexerCursorBmc::get(&ctx, &mm, name).await.unwrap(), None);
+    }
+}
+// endregion: --- Tests
@@ -1,32 +1,161 @@
 // region -- Modules
+mod access;
+pub mod audit_log;
 mod base_crud;
 pub mod bot;
 pub mod discord_profile;
 mod error;
+pub mod game_event;
+pub mod outbox;
 pub mod user;
 pub mod wallet;
 
+pub use self::access::{AccessDenied, Action};
+pub use self::base_crud::{FieldFilter, FilterOp, FilterValue, ListOptions};
 pub use self::error::{Error, Result};
-use crate::store::{new_db_pool, DbPool};
+use crate::store::{migrate, new_db_pool, DbPool};
+use sqlx::pool::PoolConnection;
+use sqlx::{PgConnection, Postgres, Transaction};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use tokio::sync::{Mutex, MutexGuard};
 pub use user::{User, UserBmc, UserForAuth, UserForCreate, UserForLogin, UserForUpdate, UserModel};
 // endregion -- Modules
 
 #[derive(Clone)]
 pub struct ModelManager {
-    pub db_pool: DbPool,
+    db_pool: DbPool,
+    txn: Option<Arc<Mutex<Option<Transaction<'static, Postgres>>>>>,
 }
 
 impl ModelManager {
-    /// Constructor
+    /// Constructor, brings the schema up to date.
     pub async fn new() -> Result<Self> {
         let db_pool = new_db_pool().await?;
+        migrate(&db_pool).await?;
 
-        Ok(ModelManager { db_pool })
+        Ok(ModelManager { db_pool, txn: None })
     }
 
-    /// Returns the sqlx db pool reference.
+    /// A model manager running every bmc call in one database transaction.
+    ///
+    /// Nothing is written until `commit_txn`, the transaction is rolled back
+    /// when the returned model manager (and its clones) are dropped.
+    pub async fn new_with_txn(&self) -> Result<ModelManager> {
+        let txn = self.db_pool.begin().await?;
+
+        Ok(ModelManager {
+            db_pool: self.db_pool.clone(),
+            txn: Some(Arc::new(Mutex::new(Some(txn)))),
+        })
+    }
+
+    /// Commit the transaction of a `new_with_txn` model manager (no-op otherwise).
+    pub async fn commit_txn(&self) -> Result<()> {
+        let Some(txn) = &self.txn else {
+            return Ok(());
+        };
+
+        match txn.lock().await.take() {
+            Some(txn) => Ok(txn.commit().await?),
+            None => Err(Error::TxnDone),
+        }
+    }
+
+    /// Connection for one bmc call, the transaction when there is one.
     /// (Only for the model layer)
-    pub(in crate::models) fn get_db_pool(&self) -> &DbPool {
-        &self.db_pool
+    ///
+    /// Drop it before calling another bmc function, the transaction (and the
+    /// single test pool connection) is held until then.
+    pub(in crate::models) async fn db_conn(&self) -> Result<DbConn<'_>> {
+        match &self.txn {
+            Some(txn) => {
+                let txn = txn.lock().await;
+                if txn.is_none() {
+                    return Err(Error::TxnDone);
+                }
+                Ok(DbConn::Txn(txn))
+            }
+            None => Ok(DbConn::Pool(self.db_pool.acquire().await?)),
+        }
+    }
+}
+
+// region:    --- Db Connection
+pub(in crate::models) enum DbConn<'a> {
+    Pool(PoolConnection<Postgres>),
+    Txn(MutexGuard<'a, Option<Transaction<'static, Postgres>>>),
+}
+
+impl Deref for DbConn<'_> {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            DbConn::Pool(conn) => conn,
+            // `db_conn` checked the transaction is still there.
+            DbConn::Txn(txn) => txn.as_ref().expect("transaction not committed"),
+        }
+    }
+}
+
+impl DerefMut for DbConn<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            DbConn::Pool(conn) => conn,
+            DbConn::Txn(txn) => txn.as_mut().expect("transaction not committed"),
+        }
+    }
+}
+// endregion: --- Db Connection
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use dotenvy::dotenv;
+    use serial_test::serial;
+
+    use super::{Error, User, UserBmc, UserForCreate};
+    use crate::{_dev_init, ctx::Ctx};
+
+    fn user_c(username: &str) -> UserForCreate {
+        UserForCreate {
+            username: Some(username.to_string()),
+            pwd: None,
+            email: None,
+        }
+    }
+
+    #[serial]
+    #[tokio::test]
+    async fn test_txn_rollback_and_commit() {
+        dotenv().ok();
+
+        let ctx = Ctx::root_ctx();
+        let mm = _dev_init::init_db_for_test().await;
+
+        // -- Dropped without commit, rolled back.
+        let mm_txn = mm.new_with_txn().await.unwrap();
+        let id = UserBmc::create(&ctx, &mm_txn, user_c("test_txn_rollback"))
+            .await
+            .unwrap();
+        drop(mm_txn);
+        assert!(matches!(
+            UserBmc::get::<User>(&ctx, &mm, id).await,
+            Err(Error::EntityNotFound { .. })
+        ));
+
+        // -- Committed.
+        let mm_txn = mm.new_with_txn().await.unwrap();
+        let id = UserBmc::create(&ctx, &mm_txn, user_c("test_txn_commit"))
+            .await
+            .unwrap();
+        mm_txn.commit_txn().await.unwrap();
+        assert!(matches!(mm_txn.commit_txn().await, Err(Error::TxnDone)));
+        drop(mm_txn);
+
+        UserBmc::get::<User>(&ctx, &mm, id).await.unwrap();
+        UserBmc::delete(&ctx, &mm, id).await.unwrap();
     }
 }
+// endregion: --- Tests
@@ -0,0 +1,419 @@
+// region:    --- Imports
+use super::base_crud::DbBmc;
+use super::ModelManager;
+use crate::ctx::Ctx;
+use crate::models::access::{self, Action};
+use crate::models::error::{Error, Result};
+use serde::Serialize;
+use sqlx::FromRow;
+// endregion: --- Imports
+
+const OUTBOX_COLUMNS: &str =
+    "id, idempotency_key, user_id, action, status, attempts, max_attempts, \
+     next_attempt_at, locked_until, digest, result, last_error, created_at, updated_at";
+
+// region:    --- Types
+#[derive(Debug, Clone, Copy, PartialEq, strum_macros::AsRefStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum OutboxStatus {
+    /// Waiting for its first or next attempt.
+    Pending,
+    /// Claimed by a worker until `locked_until`.
+    Running,
+    Done,
+    /// Out of attempts, or a failure not worth retrying.
+    Failed,
+}
+
+#[derive(Clone, FromRow, Debug, Serialize)]
+pub struct OutboxEntry {
+    pub id: i64,
+    pub idempotency_key: String,
+    pub user_id: Option<i64>,
+    pub action: String,
+    pub status: String,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub next_attempt_at: i64,
+    pub locked_until: Option<i64>,
+    pub digest: Option<String>,
+    pub result: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl OutboxEntry {
+    pub fn is(&self, status: OutboxStatus) -> bool {
+        self.status == status.as_ref()
+    }
+}
+
+pub struct OutboxForCreate {
+    pub idempotency_key: String,
+    pub user_id: Option<i64>,
+    pub action: String,
+    pub max_attempts: i32,
+}
+// endregion: --- Types
+
+pub struct OutboxBmc {}
+
+// region:    --- Outbox Controller
+impl DbBmc for OutboxBmc {
+    const TABLE: &'static str = "outbox";
+}
+
+impl OutboxBmc {
+    /// Enqueue an action, returns the id of the entry.
+    ///
+    /// Enqueuing again with the same idempotency key returns the existing
+    /// entry instead of running the action twice.
+    pub async fn enqueue(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        data: OutboxForCreate,
+        now: i64,
+    ) -> Result<i64> {
+        access::check(ctx, Self::TABLE, Action::Create, data.user_id)?;
+
+        let mut conn = mm.db_conn().await?;
+
+        let (id,) = sqlx::query_as::<_, (i64,)>(
+            "INSERT INTO outbox (idempotency_key, user_id, action, max_attempts, next_attempt_at, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $5, $5)
+             ON CONFLICT (idempotency_key) DO UPDATE SET idempotency_key = EXCLUDED.idempotency_key
+             RETURNING id",
+        )
+        .bind(data.idempotency_key)
+        .bind(data.user_id)
+        .bind(data.action)
+        .bind(data.max_attempts)
+        .bind(now)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(id)
+    }
+
+    pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<OutboxEntry> {
+        let mut conn = mm.db_conn().await?;
+
+        let entry: OutboxEntry = sqlx::query_as(&format!(
+            "SELECT {OUTBOX_COLUMNS} FROM outbox WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or(Error::EntityNotFound {
+            entity: Self::TABLE,
+            id,
+        })?;
+
+        // -- The owner is only known once read.
+        access::check(ctx, Self::TABLE, Action::Read, entry.user_id)?;
+
+        Ok(entry)
+    }
+
+    pub async fn get_by_key(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        idempotency_key: &str,
+    ) -> Result<Option<OutboxEntry>> {
+        let mut conn = mm.db_conn().await?;
+
+        let entry: Option<OutboxEntry> = sqlx::query_as(&format!(
+            "SELECT {OUTBOX_COLUMNS} FROM outbox WHERE idempotency_key = $1"
+        ))
+        .bind(idempotency_key)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        if let Some(entry) = &entry {
+            access::check(ctx, Self::TABLE, Action::Read, entry.user_id)?;
+        }
+
+        Ok(entry)
+    }
+
+    /// Give a failed entry a new round of attempts.
+    pub async fn requeue(ctx: &Ctx, mm: &ModelManager, id: i64, now: i64) -> Result<()> {
+        access::check(ctx, Self::TABLE, Action::Update, None)?;
+
+        let mut conn = mm.db_conn().await?;
+
+        let count = sqlx::query(
+            "UPDATE outbox
+             SET status = $2, attempts = 0, next_attempt_at = $4, updated_at = $4
+             WHERE id = $1 AND status = $3",
+        )
+        .bind(id)
+        .bind(OutboxStatus::Pending.as_ref())
+        .bind(OutboxStatus::Failed.as_ref())
+        .bind(now)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+        if count == 0 {
+            Err(Error::EntityNotFound {
+                entity: Self::TABLE,
+                id,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Claim the oldest due entry for `lease_sec` seconds and count the attempt.
+    ///
+    /// Entries left running by a crashed worker are failed for good once their
+    /// lease is over, the transaction may have been submitted before the crash.
+    /// `SKIP LOCKED` lets several workers poll the same table.
+    pub async fn claim_next(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        now: i64,
+        lease_sec: i64,
+    ) -> Result<Option<OutboxEntry>> {
+        access::check(ctx, Self::TABLE, Action::Update, None)?;
+
+        let mut conn = mm.db_conn().await?;
+
+        sqlx::query(
+            "UPDATE outbox
+             SET status = $2, last_error = $3, locked_until = NULL, updated_at = $4
+             WHERE status = $1 AND locked_until < $4",
+        )
+        .bind(OutboxStatus::Running.as_ref())
+        .bind(OutboxStatus::Failed.as_ref())
+        .bind("lease expired, the attempt may have landed")
+        .bind(now)
+        .execute(&mut *conn)
+        .await?;
+
+        let entry = sqlx::query_as(&format!(
+            "UPDATE outbox
+             SET status = $1, attempts = attempts + 1, locked_until = $3 + $4, updated_at = $3
+             WHERE id = (
+                 SELECT id FROM outbox
+                 WHERE status = $2 AND next_attempt_at <= $3
+                 ORDER BY id
+                 FOR UPDATE SKIP LOCKED
+                 LIMIT 1
+             )
+             RETURNING {OUTBOX_COLUMNS}"
+        ))
+        .bind(OutboxStatus::Running.as_ref())
+        .bind(OutboxStatus::Pending.as_ref())
+        .bind(now)
+        .bind(lease_sec)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(entry)
+    }
+
+    pub async fn complete(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        id: i64,
+        digest: Option<String>,
+        result: Option<String>,
+        now: i64,
+    ) -> Result<()> {
+        access::check(ctx, Self::TABLE, Action::Update, None)?;
+
+        let mut conn = mm.db_conn().await?;
+
+        sqlx::query(
+            "UPDATE outbox
+             SET status = $2, digest = $3, result = $4, locked_until = NULL, updated_at = $5
+             WHERE id = $1",
+        )
+        .bind(id)
+        .bind(OutboxStatus::Done.as_ref())
+        .bind(digest)
+        .bind(result)
+        .bind(now)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record a failed attempt, retried at `retry_at` or failed for good when `None`.
+    pub async fn fail(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        id: i64,
+        error: String,
+        result: Option<String>,
+        retry_at: Option<i64>,
+        now: i64,
+    ) -> Result<()> {
+        access::check(ctx, Self::TABLE, Action::Update, None)?;
+
+        let mut conn = mm.db_conn().await?;
+
+        let status = match retry_at {
+            Some(_) => OutboxStatus::Pending,
+            None => OutboxStatus::Failed,
+        };
+
+        sqlx::query(
+            "UPDATE outbox
+             SET status = $2, last_error = $3, result = $4,
+                 next_attempt_at = COALESCE($5, next_attempt_at), locked_until = NULL, updated_at = $6
+             WHERE id = $1",
+        )
+        .bind(id)
+        .bind(status.as_ref())
+        .bind(error)
+        .bind(result)
+        .bind(retry_at)
+        .bind(now)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+}
+// endregion: --- Outbox Controller
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use dotenvy::dotenv;
+    use serial_test::serial;
+
+    use super::{OutboxBmc, OutboxForCreate, OutboxStatus};
+    use crate::{_dev_init, ctx::Ctx};
+
+    fn entry(key: &str) -> OutboxForCreate {
+        OutboxForCreate {
+            idempotency_key: key.to_string(),
+            user_id: None,
+            action: "{}".to_string(),
+            max_attempts: 3,
+        }
+    }
+
+    #[serial]
+    #[tokio::test]
+    async fn test_outbox_enqueue_idempotent() {
+        dotenv().ok();
+
+        let ctx = Ctx::root_ctx();
+        let mm = _dev_init::init_db_for_test().await;
+
+        let id = OutboxBmc::enqueue(&ctx, &mm, entry("test_outbox_enqueue"), 100)
+            .await
+            .unwrap();
+        let id_again = OutboxBmc::enqueue(&ctx, &mm, entry("test_outbox_enqueue"), 200)
+            .await
+            .unwrap();
+
+        assert_eq!(id, id_again);
+        assert_eq!(OutboxBmc::get(&ctx, &mm, id).await.unwrap().created_at, 100);
+    }
+
+    #[serial]
+    #[tokio::test]
+    async fn test_outbox_claim_retry_complete() {
+        dotenv().ok();
+
+        let ctx = Ctx::root_ctx();
+        let mm = _dev_init::init_db_for_test().await;
+        let now = 1_000_000;
+
+        // -- Drain what other tests left due.
+        while OutboxBmc::claim_next(&ctx, &mm, now, 60)
+            .await
+            .unwrap()
+            .is_some()
+        {}
+
+        let id = OutboxBmc::enqueue(&ctx, &mm, entry("test_outbox_claim"), now)
+            .await
+            .unwrap();
+
+        // -- First attempt, failed and retried later.
+        let claimed = OutboxBmc::claim_next(&ctx, &mm, now, 60)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(claimed.id, id);
+        assert_eq!(claimed.attempts, 1);
+        assert!(claimed.is(OutboxStatus::Running));
+
+        OutboxBmc::fail(
+            &ctx,
+            &mm,
+            id,
+            "rpc down".to_string(),
+            None,
+            Some(now + 10),
+            now,
+        )
+        .await
+        .unwrap();
+        assert!(OutboxBmc::claim_next(&ctx, &mm, now + 5, 60)
+            .await
+            .unwrap()
+            .is_none());
+
+        // -- Second attempt succeeds.
+        let claimed = OutboxBmc::claim_next(&ctx, &mm, now + 10, 60)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(claimed.attempts, 2);
+
+        OutboxBmc::complete(&ctx, &mm, id, Some("digest".to_string()), None, now + 11)
+            .await
+            .unwrap();
+        let entry = OutboxBmc::get(&ctx, &mm, id).await.unwrap();
+        assert!(entry.is(OutboxStatus::Done));
+        assert_eq!(entry.digest.as_deref(), Some("digest"));
+        assert_eq!(entry.last_error.as_deref(), Some("rpc down"));
+    }
+
+    #[serial]
+    #[tokio::test]
+    async fn test_outbox_expired_lease_fails() {
+        dotenv().ok();
+
+        let ctx = Ctx::root_ctx();
+        let mm = _dev_init::init_db_for_test().await;
+        let now = 2_000_000;
+
+        // -- Drain what other tests left due.
+        while OutboxBmc::claim_next(&ctx, &mm, now, 60)
+            .await
+            .unwrap()
+            .is_some()
+        {}
+
+        let id = OutboxBmc::enqueue(&ctx, &mm, entry("test_outbox_expired_lease"), now)
+            .await
+            .unwrap();
+        let claimed = OutboxBmc::claim_next(&ctx, &mm, now, 60)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(claimed.id, id);
+
+        // -- The worker never completes it, the lease runs out.
+        assert!(OutboxBmc::claim_next(&ctx, &mm, now + 61, 60)
+            .await
+            .unwrap()
+            .is_none());
+
+        let entry = OutboxBmc::get(&ctx, &mm, id).await.unwrap();
+        assert!(entry.is(OutboxStatus::Failed));
+        assert_eq!(entry.attempts, 1);
+    }
+}
+// endregion: --- Tests
@@ -1,6 +1,7 @@
 use crate::ctx::Ctx;
 use crate::get_config;
 // use crate::pwd::{self, ContentToHash};
+use crate::models::access::{self, Action};
 use crate::models::error::{Error, Result};
 use crate::pwd::{self, ContentToHash};
 use serde::{Deserialize, Serialize};
@@ -10,7 +11,7 @@ use sqlx::FromRow;
 use tracing::info;
 use uuid::Uuid;
 
-use super::base_crud::{update, DbBmc};
+use super::base_crud::{update, DbBmc, ListOptions};
 use super::discord_profile::{DiscordProfile, DiscordProfileBmc};
 use super::wallet::{Wallet, WalletBmc};
 use super::{base_crud, ModelManager};
@@ -28,6 +29,7 @@ pub struct User {
     pub id: i64,
     pub username: Option<String>,
     pub email: Option<String>,
+    pub role: String,
 }
 
 #[derive(Clone, Fields, FromRow, Debug)]
@@ -44,6 +46,7 @@ pub struct UserForLogin {
 pub struct UserForAuth {
     pub id: i64,
     pub username: String,
+    pub role: String,
 
     pub token_salt: Uuid,
 }
@@ -72,14 +75,15 @@ pub struct UserBmc {}
 // region:    --- User Controller
 impl DbBmc for UserBmc {
     const TABLE: &'static str = "user";
+    const ID_IS_OWNER: bool = true;
 }
 
 impl UserBmc {
-    pub async fn list<E>(ctx: &Ctx, mm: &ModelManager) -> Result<Vec<E>>
+    pub async fn list<E>(ctx: &Ctx, mm: &ModelManager, options: ListOptions) -> Result<Vec<E>>
     where
         E: UserModel,
     {
-        base_crud::list::<Self, E>(ctx, mm).await
+        base_crud::list::<Self, E>(ctx, mm, options).await
     }
 
     pub async fn get<E>(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<E>
@@ -129,12 +133,22 @@ impl UserBmc {
     where
         E: UserModel,
     {
-        let db_pool = mm.get_db_pool();
+        let mut conn = mm.db_conn().await?;
+
+        // -- The owner of the row (the user), checked before reading it.
+        let owner: Option<i64> = sqlx::query_scalar(r#"SELECT id FROM "user" WHERE username = $1"#)
+            .bind(username)
+            .fetch_optional(&mut *conn)
+            .await?;
+        let Some(owner) = owner else {
+            return Ok(None);
+        };
+        access::check(ctx, Self::TABLE, Action::Read, Some(owner))?;
 
         let user = sqlb::select()
             .table(Self::TABLE)
             .and_where("username", "=", username)
-            .fetch_optional::<_, E>(db_pool)
+            .fetch_optional::<_, E>(&mut *conn)
             .await?;
 
         Ok(user)
@@ -144,12 +158,14 @@ impl UserBmc {
     where
         E: UserModel,
     {
-        let db_pool = mm.get_db_pool();
+        access::check(ctx, Self::TABLE, Action::Read, Some(id))?;
+
+        let mut conn = mm.db_conn().await?;
 
         let user = sqlb::select()
             .table(Self::TABLE)
             .and_where("id", "=", id)
-            .fetch_optional::<_, E>(db_pool)
+            .fetch_optional::<_, E>(&mut *conn)
             .await?;
 
         Ok(user)
@@ -1,8 +1,10 @@
 // region:    --- Imports
 use super::base_crud::{update, DbBmc};
 use super::{base_crud, ModelManager};
+use crate::crypt;
 use crate::ctx::Ctx;
 use crate::get_config;
+use crate::models::access::{self, Action};
 use crate::models::error::{Error, Result};
 use crate::pwd::{self, ContentToHash};
 use serde::{Deserialize, Serialize};
@@ -22,6 +24,16 @@ pub struct Wallet {
     pub last_faucet: Option<i64>,
 }
 
+/// Wallet with its recovery phrase, only for rebuilding the signing key.
+/// (the phrase is the encrypted envelope unless read with `WalletBmc::get_for_sign`)
+#[derive(Clone, Fields, FromRow)]
+pub struct WalletForSign {
+    pub id: i64,
+    pub pub_key: String,
+    pub sign_type: String,
+    pub phrase: String,
+}
+
 #[derive(Deserialize, Fields)]
 pub struct WalletForCreate {
     pub id: i64,
@@ -35,17 +47,24 @@ pub struct WalletForCreate {
 pub struct WalletForUpdateFaucet {
     pub last_faucet: i64,
 }
+
+#[derive(Fields)]
+struct WalletForUpdatePhrase {
+    phrase: String,
+}
 // endregion:    --- Types
 
 pub trait WalletModel: HasFields + for<'r> FromRow<'r, PgRow> + Unpin + Send {}
 
 impl WalletModel for Wallet {}
+impl WalletModel for WalletForSign {}
 
 pub struct WalletBmc {}
 
 // region:    --- Discord Profile Controller
 impl DbBmc for WalletBmc {
     const TABLE: &'static str = "wallet";
+    const ID_IS_OWNER: bool = true;
 }
 
 impl WalletBmc {
@@ -56,7 +75,56 @@ impl WalletBmc {
         base_crud::get::<Self, E>(ctx, mm, id).await
     }
 
+    pub async fn get_by_pub_key<E>(ctx: &Ctx, mm: &ModelManager, pub_key: &str) -> Result<E>
+    where
+        E: WalletModel,
+    {
+        let mut conn = mm.db_conn().await?;
+
+        // -- The owner of the row, checked before reading it.
+        let owner: i64 = sqlx::query_scalar("SELECT id FROM wallet WHERE pub_key = $1")
+            .bind(pub_key)
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or(Error::EntityNotFoundString {
+                entity: Self::TABLE,
+                id: pub_key.to_string(),
+            })?;
+        access::check(ctx, Self::TABLE, Action::Read, Some(owner))?;
+
+        let entity: E = sqlb::select()
+            .table(Self::TABLE)
+            .columns(E::field_names())
+            .and_where("pub_key", "=", pub_key)
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or(Error::EntityNotFoundString {
+                entity: Self::TABLE,
+                id: pub_key.to_string(),
+            })?;
+
+        Ok(entity)
+    }
+
+    /// Wallet with the decrypted recovery phrase, for the root ctx only.
+    pub async fn get_for_sign(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        pub_key: &str,
+    ) -> Result<WalletForSign> {
+        let mut wallet = Self::get_by_pub_key::<WalletForSign>(ctx, mm, pub_key).await?;
+        access::check(ctx, Self::TABLE, Action::Decrypt, Some(wallet.id))?;
+        wallet.phrase = crypt::decrypt_phrase(&wallet.phrase)?;
+
+        Ok(wallet)
+    }
+
     pub async fn create(ctx: &Ctx, mm: &ModelManager, data: WalletForCreate) -> Result<i64> {
+        let data = WalletForCreate {
+            phrase: crypt::encrypt_phrase(&data.phrase)?,
+            ..data
+        };
+
         base_crud::create::<WalletBmc, WalletForCreate>(ctx, mm, data).await
     }
 
@@ -69,9 +137,45 @@ impl WalletBmc {
         base_crud::update::<WalletBmc, WalletForUpdateFaucet>(ctx, mm, id, data).await
     }
 
-    async fn delete(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+    pub async fn delete(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
         base_crud::delete::<WalletBmc>(ctx, mm, id).await
     }
+
+    /// One-shot migration, seal the plaintext phrases and the phrases of
+    /// rotated out keys with the active wallet key.
+    /// Returns the number of re-encrypted wallets.
+    pub async fn encrypt_phrases(ctx: &Ctx, mm: &ModelManager) -> Result<usize> {
+        access::check(ctx, Self::TABLE, Action::Update, None)?;
+
+        let mut conn = mm.db_conn().await?;
+
+        let wallets: Vec<WalletForSign> = sqlx::query_as(
+            "SELECT id, pub_key, sign_type, phrase FROM wallet WHERE NOT starts_with(phrase, $1)",
+        )
+        .bind(crypt::active_envelope_prefix()?)
+        .fetch_all(&mut *conn)
+        .await?;
+        // -- The updates below take the connection (or transaction) again.
+        drop(conn);
+
+        let mut count = 0;
+        for wallet in wallets {
+            let Some(phrase) = crypt::reencrypt_phrase(&wallet.phrase)? else {
+                continue;
+            };
+
+            base_crud::update::<WalletBmc, WalletForUpdatePhrase>(
+                ctx,
+                mm,
+                wallet.id,
+                WalletForUpdatePhrase { phrase },
+            )
+            .await?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
 }
 // endregion:    --- Discord Profile Controller
 
@@ -80,13 +184,13 @@ impl WalletBmc {
 mod tests {
     use crate::{
         _dev_init,
-        ctx::Ctx,
-        models::{wallet::WalletForUpdateFaucet, ModelManager, UserBmc, UserForCreate},
+        ctx::{Ctx, Role},
+        models::{wallet::WalletForUpdateFaucet, Error, ModelManager, UserBmc, UserForCreate},
     };
     use dotenvy::dotenv;
     use serial_test::serial;
 
-    use super::{Wallet, WalletBmc, WalletForCreate};
+    use super::{Wallet, WalletBmc, WalletForCreate, WalletForSign};
 
     #[serial]
     #[tokio::test]
@@ -128,6 +232,33 @@ mod tests {
 
         assert_eq!(wallet.last_faucet, None);
 
+        // -- The phrase is stored encrypted.
+        let stored = WalletBmc::get_by_pub_key::<WalletForSign>(&ctx, &mm, "pubkey1")
+            .await
+            .unwrap();
+
+        assert_ne!(stored.phrase, "ab cd");
+
+        let wallet_for_sign = WalletBmc::get_for_sign(&ctx, &mm, "pubkey1").await.unwrap();
+
+        assert_eq!(wallet_for_sign.id, wallet_id);
+        assert_eq!(wallet_for_sign.phrase, "ab cd");
+
+        // -- An admin reads the wallet, not its phrase.
+        let admin = Ctx::new(user_id, Role::Admin).unwrap();
+        assert!(
+            WalletBmc::get_by_pub_key::<WalletForSign>(&admin, &mm, "pubkey1")
+                .await
+                .is_ok()
+        );
+        assert!(matches!(
+            WalletBmc::get_for_sign(&admin, &mm, "pubkey1").await,
+            Err(Error::AccessDenied(_))
+        ));
+
+        // -- Nothing left to migrate.
+        assert_eq!(WalletBmc::encrypt_phrases(&ctx, &mm).await.unwrap(), 0);
+
         WalletBmc::update(
             &ctx,
             &mm,
@@ -6,7 +6,8 @@ use sui_types::base_types::ObjectIDParseError;
 use tracing::debug;
 
 use crate::middlewares::error::CtxExtError;
-use crate::{middlewares, models, pwd, routes, token};
+use crate::sui_call::call_api::{self, GameError};
+use crate::{middlewares, models, pwd, routes, token, worker};
 
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -17,7 +18,6 @@ pub enum Error {
     RpcMethodUnknown(String),
     RpcMissingParams { rpc_method: String },
     RpcFailJsonParams { rpc_method: String },
-    RpcNoPermission,
 
     // -- Login
     LoginFailUsernameNotFound,
@@ -31,6 +31,8 @@ pub enum Error {
     Model(models::Error),
     Pwd(pwd::Error),
     Token(token::Error),
+    SuiCall(call_api::Error),
+    Worker(worker::Error),
 
     // -- External Modules
     SerdeJson(String),
@@ -66,6 +68,18 @@ impl From<token::Error> for Error {
     }
 }
 
+impl From<call_api::Error> for Error {
+    fn from(val: call_api::Error) -> Self {
+        Self::SuiCall(val)
+    }
+}
+
+impl From<worker::Error> for Error {
+    fn from(val: worker::Error) -> Self {
+        Self::Worker(val)
+    }
+}
+
 impl From<serde_json::Error> for Error {
     fn from(val: serde_json::Error) -> Self {
         Self::SerdeJson(val.to_string())
@@ -127,7 +141,7 @@ impl Error {
             }
 
             SignUpFailedUserAlreadyExist(username) => (
-                StatusCode::CREATED,
+                StatusCode::CONFLICT,
                 ClientError::SIGN_UP_FAIL(username.clone()),
             ),
 
@@ -139,6 +153,19 @@ impl Error {
                 StatusCode::BAD_REQUEST,
                 ClientError::ENTITY_NOT_FOUND { entity, id: *id },
             ),
+            Model(models::Error::AccessDenied(_)) => {
+                (StatusCode::FORBIDDEN, ClientError::NO_PERMISSION)
+            }
+            Model(models::Error::ListFieldUnknown { field }) => (
+                StatusCode::BAD_REQUEST,
+                ClientError::LIST_FIELD_UNKNOWN(field.clone()),
+            ),
+
+            // -- Game
+            SuiCall(call_api::Error::GameRejected { game_error, .. })
+            | Worker(worker::Error::SuiCall(call_api::Error::GameRejected {
+                game_error, ..
+            })) => game_status_and_error(*game_error),
 
             // -- Fallback.
             _ => (
@@ -149,16 +176,37 @@ impl Error {
     }
 }
 
+fn game_status_and_error(game_error: GameError) -> (StatusCode, ClientError) {
+    match game_error {
+        GameError::NotOwner => (StatusCode::FORBIDDEN, ClientError::NOT_OWNER),
+        GameError::NotEnoughCoin => (StatusCode::BAD_REQUEST, ClientError::NOT_ENOUGH_COIN),
+        GameError::PetFainted => (StatusCode::BAD_REQUEST, ClientError::PET_FAINTED),
+        GameError::BotAlreadyDefeated => {
+            (StatusCode::BAD_REQUEST, ClientError::BOT_ALREADY_DEFEATED)
+        }
+        GameError::InsufficientLevel => (StatusCode::BAD_REQUEST, ClientError::INSUFFICIENT_LEVEL),
+    }
+}
+
 #[derive(Debug, Serialize, strum_macros::AsRefStr)]
 #[serde(tag = "message", content = "detail")]
 #[allow(non_camel_case_types)]
 pub enum ClientError {
     LOGIN_FAIL,
     NO_AUTH,
+    NO_PERMISSION,
     ENTITY_NOT_FOUND { entity: &'static str, id: i64 },
+    LIST_FIELD_UNKNOWN(String),
 
     SIGN_UP_FAIL(String),
 
+    // -- Game
+    NOT_OWNER,
+    NOT_ENOUGH_COIN,
+    PET_FAINTED,
+    BOT_ALREADY_DEFEATED,
+    INSUFFICIENT_LEVEL,
+
     SERVICE_ERROR,
 }
 // endregion: --- Client Error
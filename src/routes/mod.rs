@@ -3,20 +3,43 @@ pub mod routes_login;
 pub mod routes_static;
 pub mod rpc;
 
+use axum::extract::FromRef;
 use axum::routing::post;
 use axum::{middleware, Router};
 
 use crate::middlewares::mw_ctx_require::mw_ctx_require;
 use crate::models::ModelManager;
+use crate::sui_call::call_api::SuiExecutor;
 
 pub use self::error::ClientError;
 pub use self::error::{Error, Result};
 use self::rpc::handler::rpc_hanler;
 
-pub fn routes(mm: ModelManager) -> Router {
-    Router::new().nest("/auth", routes_login::routes(mm.clone()))
-    // .nest(
-    //     "/api",
-    //     rpc::routes(mm.clone()).route_layer(middleware::from_fn(mw_ctx_require)),
-    // )
+// region:    --- App State
+#[derive(Clone)]
+pub struct AppState {
+    pub mm: ModelManager,
+    pub executor: SuiExecutor,
+}
+
+impl FromRef<AppState> for ModelManager {
+    fn from_ref(state: &AppState) -> Self {
+        state.mm.clone()
+    }
+}
+
+impl FromRef<AppState> for SuiExecutor {
+    fn from_ref(state: &AppState) -> Self {
+        state.executor.clone()
+    }
+}
+// endregion: --- App State
+
+pub fn routes(state: AppState) -> Router {
+    Router::new()
+        .nest("/auth", routes_login::routes(state.clone()))
+        .nest(
+            "/api",
+            rpc::routes(state.mm.clone()).route_layer(middleware::from_fn(mw_ctx_require)),
+        )
 }
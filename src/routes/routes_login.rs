@@ -29,28 +29,23 @@ use crate::{
     ctx::Ctx,
     get_config,
     models::{
-        bot::{BotBmc, BotForCreate},
+        self,
         discord_profile::{self, DiscordProfile, DiscordProfileBmc, DiscordProfileForCreate},
-        wallet::{WalletBmc, WalletForCreate},
+        wallet::{Wallet, WalletBmc, WalletForCreate},
         ModelManager, UserForAuth, UserForCreate, UserForLogin,
     },
     pwd::{self, ContentToHash},
     sui_call::{
-        call_api::{
-            create_bot::{self, create_bot},
-            create_profile::create_profile,
-        },
-        read_api::owned_objects::WePetGame,
-        sui_move_object::bot_obj::SuiBotObject,
-        utils::request_tokens_from_faucet,
-        BOT_OBJECT_NAME,
+        call_api::SuiExecutor, read_api::owned_objects::WePetGame,
+        sui_move_object::bot_obj::SuiBotObject, utils::request_tokens_from_faucet, BOT_OBJECT_NAME,
     },
     token::{create_token, Token},
     utils::time::unix_timestamp,
+    worker,
 };
 use crate::{
     models::{User, UserBmc},
-    routes::{Error, Result},
+    routes::{AppState, Error, Result},
 };
 
 use std::fs::File;
@@ -61,16 +56,17 @@ use tokio::io::AsyncReadExt;
 use super::routes_static::welcome;
 // endregion: --- Imports
 
-pub fn routes(mm: ModelManager) -> Router {
+pub fn routes(state: AppState) -> Router {
     Router::new()
         .route("/register", get(register_hanlder))
-        .with_state(mm)
+        .with_state(state)
 }
 
 // region:    --- Signup
 async fn register_hanlder(
     query: Query<CodeQuery>,
     State(mm): State<ModelManager>,
+    State(executor): State<SuiExecutor>,
 ) -> Result<Response> {
     debug!("{:<12} - register_handler", "HANDLER");
     let root_ctx = Ctx::root_ctx();
@@ -83,7 +79,7 @@ async fn register_hanlder(
     let user_info = get_user_info(token.as_str(), config).await?;
 
     // create new user
-    let res = _register_handler(&root_ctx, &mm, user_info, config).await?;
+    let res = _register_handler(&root_ctx, &mm, &executor, user_info, config).await?;
 
     Ok(res.into_response())
 }
@@ -139,26 +135,40 @@ async fn get_user_info(code: &str, config: &Config) -> Result<DiscordUserInfReso
 async fn _register_handler(
     ctx: &Ctx,
     mm: &ModelManager,
+    executor: &SuiExecutor,
     user_info: DiscordUserInfResonse,
     config: &Config,
 ) -> Result<Html<String>> {
-    // check user exist
+    // check user exist, a partial account is resumed
     match DiscordProfileBmc::get_by_discord_id::<DiscordProfile>(ctx, mm, user_info.id).await {
-        Ok(_) => {
-            Error::SignUpFailedUserAlreadyExist(user_info.username.clone());
+        Ok(discord_profile) => {
+            return resume_register(ctx, mm, discord_profile.id, &user_info.username).await;
         }
-        Err(e) => (),
+        Err(models::Error::EntityNotFound { .. }) => (),
+        Err(e) => return Err(e.into()),
     }
 
+    // create wallet (the key is only used once the account is committed)
+    let (address, phrase, scheme) = executor.keystore().generate_key().await?;
+
+    let sign_type = match scheme {
+        SignatureScheme::ED25519 => "ed25519",
+        _ => "",
+    }
+    .to_string();
+
+    // db writes and chain steps queue - one transaction
+    let mm_txn = mm.new_with_txn().await?;
+
     // create user
     let user_c = UserForCreate {
         username: Some(user_info.username.clone()),
         pwd: None,
         email: None,
     };
-    let user_id = UserBmc::create(ctx, mm, user_c).await?;
+    let user_id = UserBmc::create(ctx, &mm_txn, user_c).await?;
 
-    // create discord profile
+    // create discord profile, `discord_id` is unique (concurrent sign up)
     let discord_profile_c = DiscordProfileForCreate {
         id: user_id,
         discord_id: user_info.id,
@@ -166,24 +176,14 @@ async fn _register_handler(
         global_name: user_info.global_name,
         avatar: user_info.avatar,
     };
-    let d_id = DiscordProfileBmc::create(ctx, mm, discord_profile_c).await?;
-
-    // create wallet & faucet
-    let keystore_path = Path::new("/home/ganzzi/.sui/sui_config/sui.keystore");
-    let mut keystore =
-        Keystore::from(FileBasedKeystore::new(&keystore_path.to_path_buf()).unwrap());
-
-    let (address, phrase, scheme) = keystore
-        .generate_and_add_new_key(SignatureScheme::ED25519, None, None)
-        .unwrap();
-
-    let sign_type = match scheme {
-        SignatureScheme::ED25519 => "ed25519",
-        _ => "",
-    }
-    .to_string();
-
-    let sui_client = SuiClientBuilder::default().build_devnet().await.unwrap();
+    DiscordProfileBmc::create(ctx, &mm_txn, discord_profile_c)
+        .await
+        .map_err(|e| match e {
+            models::Error::UniqueViolation { .. } => {
+                Error::SignUpFailedUserAlreadyExist(user_info.username.clone())
+            }
+            e => e.into(),
+        })?;
 
     let wallet_c = WalletForCreate {
         id: user_id,
@@ -192,34 +192,46 @@ async fn _register_handler(
         phrase,
         last_faucet: Some(unix_timestamp()),
     };
-    WalletBmc::create(ctx, mm, wallet_c).await?;
-
-    // create hero
-    let pkg = &ObjectID::from_str(&config.PACKAGE)?;
-    // create pet
-    create_profile(&sui_client, pkg, address, &keystore)
-        .await
-        .map_err(|e| debug!("Error: {e:?}"));
+    WalletBmc::create(ctx, &mm_txn, wallet_c).await?;
 
-    // create bot
-    let obj_id = create_bot(&sui_client, pkg, address, 100, 8, &keystore)
-        .await
-        .map_err(|e| debug!("Error: {e:?}"))
-        .unwrap();
+    // queue the chain calls, the outbox worker retries them on failure
+    for (key, action) in worker::register_steps(user_id, address) {
+        worker::enqueue(ctx, &mm_txn, key, Some(user_id), &action).await?;
+    }
 
-    let bot_c = BotForCreate {
-        bot_id: obj_id.to_string(),
-        user_id,
-        last_creation: unix_timestamp(),
-    };
-    BotBmc::create(ctx, mm, bot_c).await?;
+    mm_txn.commit_txn().await?;
 
     // response - html
-    let res = welcome(&user_info.username, Some("You have succesfully registered new account! request some SUI faucet and comeback to discord and start playing game!"), Some(address.to_string().as_str())).await;
+    let res = welcome(&user_info.username, Some("You have succesfully registered new account! your hero, pet and first bot are on their way. request some SUI faucet and comeback to discord and start playing game!"), Some(address.to_string().as_str())).await;
 
     Ok((res))
 }
 
+/// Complete the chain steps of a partial account, see `worker::resume_register`.
+async fn resume_register(
+    ctx: &Ctx,
+    mm: &ModelManager,
+    user_id: i64,
+    username: &str,
+) -> Result<Html<String>> {
+    let wallet = WalletBmc::get::<Wallet>(ctx, mm, user_id).await?;
+    let address = SuiAddress::from_str(&wallet.pub_key)
+        .map_err(|e| Error::ObjectIDParseError(e.to_string()))?;
+
+    if !worker::resume_register(ctx, mm, user_id, address).await? {
+        return Err(Error::SignUpFailedUserAlreadyExist(username.to_string()));
+    }
+
+    let res = welcome(
+        username,
+        Some("Your account is being completed, your hero, pet and first bot are on their way. comeback to discord in a moment!"),
+        Some(wallet.pub_key.as_str()),
+    )
+    .await;
+
+    Ok(res)
+}
+
 #[derive(Debug, serde::Deserialize)]
 struct CodeQuery {
     code: String,
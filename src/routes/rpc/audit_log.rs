@@ -0,0 +1,18 @@
+use crate::{
+    ctx::Ctx,
+    models::{
+        audit_log::{AuditLog, AuditLogBmc},
+        ModelManager,
+    },
+    routes::error::Result,
+};
+
+use super::params::ParamsForUserList;
+
+pub async fn list_audit_logs(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForUserList,
+) -> Result<Vec<AuditLog>> {
+    Ok(AuditLogBmc::list_by_user(&ctx, &mm, params.user_id, params.list_options).await?)
+}
@@ -1,5 +1,5 @@
 use crate::routes::error::Result;
-use crate::routes::rpc::{delete_user, get_user, list_users, params, update_user};
+use crate::routes::rpc::{delete_user, get_user, list_audit_logs, list_users, params, update_user};
 use crate::routes::Error;
 use crate::{ctx::Ctx, models::ModelManager};
 use axum::response::IntoResponse;
@@ -74,11 +74,15 @@ pub async fn _rpc_handler(
     let result_json: Value = match rpc_method.as_str() {
         // -- Task RPC methods.
         // "create_task" => exec_rpc_fn!(create_task, ctx, mm, rpc_params),
-        "list_users" => exec_rpc_fn!(list_users, ctx, mm),
+        // list params are optional, no params lists the first page
+        "list_users" => exec_rpc_fn!(list_users, ctx, mm, rpc_params.or(Some(json!({})))),
         "update_user" => exec_rpc_fn!(update_user, ctx, mm, rpc_params),
         "get_user" => exec_rpc_fn!(get_user, ctx, mm, rpc_params),
         "delete_user" => exec_rpc_fn!(delete_user, ctx, mm, rpc_params),
 
+        // -- Audit RPC methods (admin).
+        "list_audit_logs" => exec_rpc_fn!(list_audit_logs, ctx, mm, rpc_params),
+
         // -- Fallback as Err.
         _ => return Err(Error::RpcMethodUnknown(rpc_method)),
     };
@@ -90,3 +94,61 @@ pub async fn _rpc_handler(
 
     Ok(Json(body_response))
 }
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use dotenvy::dotenv;
+    use serde_json::json;
+    use serial_test::serial;
+
+    use super::{_rpc_handler, RpcRequest};
+    use crate::_dev_init;
+    use crate::ctx::Ctx;
+    use crate::models::{UserBmc, UserForCreate};
+
+    #[serial]
+    #[tokio::test]
+    async fn test_rpc_list_users_page() {
+        dotenv().ok();
+
+        let ctx = Ctx::root_ctx();
+        let mm = _dev_init::init_db_for_test().await;
+
+        let mut ids = Vec::new();
+        for i in 0..3 {
+            let user_c = UserForCreate {
+                username: Some(format!("test_rpc_list_users_{i}")),
+                pwd: None,
+                email: None,
+            };
+            ids.push(UserBmc::create(&ctx, &mm, user_c).await.unwrap());
+        }
+
+        // -- Newest first, second page of one.
+        let rpc_request = RpcRequest {
+            id: Some(json!(1)),
+            method: "list_users".to_string(),
+            params: Some(json!({
+                "list_options": {
+                    "filters": [{ "field": "username", "op": "like", "value": "test_rpc_list_users_%" }],
+                    "order_by": "!id",
+                    "limit": 1,
+                    "offset": 1
+                }
+            })),
+        };
+        let res = _rpc_handler(mm.clone(), ctx.clone(), rpc_request)
+            .await
+            .unwrap();
+
+        let users = res.0["result"].as_array().unwrap().clone();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0]["id"], json!(ids[1]));
+
+        for id in ids {
+            UserBmc::delete(&ctx, &mm, id).await.unwrap();
+        }
+    }
+}
+// endregion: --- Tests
@@ -1,3 +1,4 @@
+mod audit_log;
 pub mod handler;
 mod params;
 mod user;
@@ -12,6 +13,7 @@ use serde_json::{json, Value};
 use tracing::debug;
 
 use self::handler::rpc_hanler;
+pub use self::audit_log::*;
 pub use self::handler::RpcInfo;
 pub use self::user::*;
 
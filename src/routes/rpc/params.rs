@@ -1,5 +1,7 @@
 use serde::Deserialize;
 
+use crate::models::ListOptions;
+
 #[derive(Deserialize)]
 pub struct ParamsForCreate<D> {
     pub data: D,
@@ -15,3 +17,16 @@ pub struct ParamsForUpdate<D> {
 pub struct ParamsForJustId {
     pub id: i64,
 }
+
+#[derive(Deserialize)]
+pub struct ParamsForUserList {
+    pub user_id: i64,
+    #[serde(default)]
+    pub list_options: ListOptions,
+}
+
+#[derive(Deserialize, Default)]
+pub struct ParamsList {
+    #[serde(default)]
+    pub list_options: ListOptions,
+}
@@ -1,14 +1,14 @@
 use crate::{
     ctx::Ctx,
     models::{ModelManager, User, UserBmc, UserForCreate, UserForUpdate},
-    routes::error::{Error, Result},
+    routes::error::Result,
 };
 use axum::response::Response;
 
-use super::params::{ParamsForCreate, ParamsForJustId, ParamsForUpdate};
+use super::params::{ParamsForCreate, ParamsForJustId, ParamsForUpdate, ParamsList};
 
-pub async fn list_users(ctx: Ctx, mm: ModelManager) -> Result<Vec<User>> {
-    Ok(UserBmc::list::<User>(&ctx, &mm).await?)
+pub async fn list_users(ctx: Ctx, mm: ModelManager, params: ParamsList) -> Result<Vec<User>> {
+    Ok(UserBmc::list::<User>(&ctx, &mm, params.list_options).await?)
 }
 
 pub async fn get_user(ctx: Ctx, mm: ModelManager, params: ParamsForJustId) -> Result<User> {
@@ -20,17 +20,11 @@ pub async fn update_user(
     mm: ModelManager,
     params: ParamsForUpdate<UserForUpdate>,
 ) -> Result<()> {
-    if ctx.user_id() != params.id {
-        return Err(Error::RpcNoPermission);
-    }
     UserBmc::update(&ctx, &mm, params.id, params.data).await?;
     Ok(())
 }
 
 pub async fn delete_user(ctx: Ctx, mm: ModelManager, params: ParamsForJustId) -> Result<()> {
-    if ctx.user_id() != params.id {
-        return Err(Error::RpcNoPermission);
-    }
     UserBmc::delete(&ctx, &mm, params.id).await?;
     Ok(())
 }
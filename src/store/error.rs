@@ -5,6 +5,7 @@ pub type Result<T> = core::result::Result<T, Error>;
 #[derive(Debug, Serialize)]
 pub enum Error {
 	FailToCreatePool(String),
+	FailToMigrate(String),
 }
 
 // region:    --- Error Boilerplate
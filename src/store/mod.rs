@@ -20,3 +20,14 @@ pub async fn new_db_pool() -> Result<DbPool> {
         .await
         .map_err(|ex| Error::FailToCreatePool(ex.to_string()))
 }
+
+/// Apply the pending `migrations/` (embedded at build time), in every environment.
+///
+/// Applied versions and their checksums are kept in `_sqlx_migrations`,
+/// an applied migration edited afterwards fails the startup.
+pub async fn migrate(db_pool: &DbPool) -> Result<()> {
+    sqlx::migrate!()
+        .run(db_pool)
+        .await
+        .map_err(|ex| Error::FailToMigrate(ex.to_string()))
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::gas::MoveAbort;
+use crate::sui_call::MODULE_NAME;
+
+/// A failure of a game rule, told to the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameError {
+    NotOwner,
+    NotEnoughCoin,
+    PetFainted,
+    BotAlreadyDefeated,
+    InsufficientLevel,
+}
+
+/// From the `we_pet_game` abort codes and the execution failure statuses to
+/// the game errors.
+///
+/// Loaded from the json file of `ABORT_CODES_FILE` (see `abort_codes.sample.json`),
+/// the file replaces the defaults. A failure not in the catalog stays a raw failure.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AbortCatalog {
+    #[serde(default)]
+    codes: HashMap<u64, GameError>,
+    /// Keyed by the status name, e.g. `InsufficientCoinBalance`.
+    #[serde(default)]
+    statuses: HashMap<String, GameError>,
+}
+
+impl Default for AbortCatalog {
+    /// No abort code, they depend on the deployed `we_pet_game` package. Only
+    /// the sui failure statuses.
+    fn default() -> Self {
+        AbortCatalog {
+            codes: HashMap::new(),
+            statuses: HashMap::from([(
+                "InsufficientCoinBalance".to_string(),
+                GameError::NotEnoughCoin,
+            )]),
+        }
+    }
+}
+
+impl AbortCatalog {
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// The game error of an execution failure status, `None` when not in the catalog.
+    ///
+    /// Only the aborts of the game module are decoded, the codes of other
+    /// modules (e.g. `0x2::coin`) mean something else.
+    pub fn decode(&self, error: &str) -> Option<GameError> {
+        match MoveAbort::parse(error) {
+            Some(abort) if abort.module == MODULE_NAME => self.codes.get(&abort.code).copied(),
+            Some(_) => None,
+            None => self.statuses.get(status_name(error)).copied(),
+        }
+    }
+}
+
+/// The leading name of a failure status, e.g. `InsufficientGas` for
+/// `InsufficientGas in command 0`.
+fn status_name(error: &str) -> &str {
+    let end = error
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .unwrap_or(error.len());
+
+    &error[..end]
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::{AbortCatalog, GameError};
+
+    const HUNTBOT_ABORT: &str = r#"MoveAbort(MoveLocation { module: ModuleId { address: 0000000000000000000000000000000000000000000000000000000000000abc, name: Identifier("we_pet_game") }, function: 4, instruction: 20, function_name: Some("huntbot") }, 4) in command 0"#;
+
+    #[test]
+    fn test_decode_default_ok() {
+        let catalog = AbortCatalog::default();
+
+        assert_eq!(catalog.decode(HUNTBOT_ABORT), None);
+        assert_eq!(
+            catalog.decode("InsufficientCoinBalance in command 1"),
+            Some(GameError::NotEnoughCoin)
+        );
+        assert_eq!(catalog.decode("InsufficientGas"), None);
+    }
+
+    #[test]
+    fn test_decode_sample_ok() {
+        let catalog =
+            AbortCatalog::from_json(include_str!("../../../abort_codes.sample.json")).unwrap();
+
+        assert_eq!(
+            catalog.decode(HUNTBOT_ABORT),
+            Some(GameError::BotAlreadyDefeated)
+        );
+    }
+
+    #[test]
+    fn test_decode_other_module_none() {
+        let catalog =
+            AbortCatalog::from_json(include_str!("../../../abort_codes.sample.json")).unwrap();
+        let error = HUNTBOT_ABORT.replace("we_pet_game", "coin");
+
+        assert_eq!(catalog.decode(&error), None);
+    }
+
+    #[test]
+    fn test_from_json_replaces_defaults() {
+        let catalog = AbortCatalog::from_json(r#"{ "codes": { "4": "PetFainted" } }"#).unwrap();
+
+        assert_eq!(catalog.decode(HUNTBOT_ABORT), Some(GameError::PetFainted));
+        assert_eq!(catalog.decode("InsufficientCoinBalance"), None);
+    }
+}
+// endregion: --- Tests
@@ -0,0 +1,296 @@
+use std::collections::BTreeMap;
+
+use move_core_types::language_storage::StructTag;
+use sui_json_rpc_types::{
+    ObjectChange, SuiEvent, SuiExecutionStatus, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponse,
+};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use sui_types::gas::GasCostSummary;
+use sui_types::object::Owner;
+
+use super::error::{Error, Result};
+
+// region:    --- Changed Objects
+
+#[derive(Debug, Clone)]
+pub struct ChangedObject {
+    pub object_id: ObjectID,
+    pub object_type: StructTag,
+    /// Owner after the transaction, `None` for a deleted object.
+    pub owner: Option<Owner>,
+}
+
+impl ChangedObject {
+    /// The address owning the object, `None` when shared, immutable or deleted.
+    pub fn owner_address(&self) -> Option<SuiAddress> {
+        match self.owner {
+            Some(Owner::AddressOwner(address)) => Some(address),
+            _ => None,
+        }
+    }
+}
+
+/// Changed objects grouped by their move type.
+#[derive(Debug, Clone, Default)]
+pub struct ObjectsByType(BTreeMap<StructTag, Vec<ChangedObject>>);
+
+impl ObjectsByType {
+    pub fn push(&mut self, object: ChangedObject) {
+        self.0
+            .entry(object.object_type.clone())
+            .or_default()
+            .push(object);
+    }
+
+    /// The objects of exactly `object_type`, other packages and modules excluded.
+    pub fn of_type(&self, object_type: &StructTag) -> &[ChangedObject] {
+        self.0
+            .get(object_type)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ChangedObject> {
+        self.0.values().flatten()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<ChangedObject> for ObjectsByType {
+    fn from_iter<I: IntoIterator<Item = ChangedObject>>(iter: I) -> Self {
+        let mut objects = ObjectsByType::default();
+        for object in iter {
+            objects.push(object);
+        }
+
+        objects
+    }
+}
+// endregion: --- Changed Objects
+
+// region:    --- Call Effects
+
+/// The parsed outcome of an executed transaction.
+///
+/// Look the game objects up by their full type (`SuiExecutor::game_type`),
+/// an object of the same name from another package never matches.
+#[derive(Debug)]
+pub struct CallEffects {
+    pub digest: TransactionDigest,
+    pub status: SuiExecutionStatus,
+    pub created: ObjectsByType,
+    pub mutated: ObjectsByType,
+    /// Objects sent to a new owner, without other change.
+    pub transferred: ObjectsByType,
+    pub deleted: ObjectsByType,
+    pub gas_used: GasCostSummary,
+    pub events: Vec<SuiEvent>,
+}
+
+impl CallEffects {
+    /// The created object of `object_type`, an error unless there is exactly one.
+    pub fn created_object(&self, object_type: &StructTag) -> Result<&ChangedObject> {
+        self.single("created", &self.created, object_type)
+    }
+
+    /// The mutated object of `object_type`, an error unless there is exactly one.
+    pub fn mutated_object(&self, object_type: &StructTag) -> Result<&ChangedObject> {
+        self.single("mutated", &self.mutated, object_type)
+    }
+
+    /// The deleted object of `object_type`, an error unless there is exactly one.
+    pub fn deleted_object(&self, object_type: &StructTag) -> Result<&ChangedObject> {
+        self.single("deleted", &self.deleted, object_type)
+    }
+
+    fn single<'a>(
+        &self,
+        change: &'static str,
+        objects: &'a ObjectsByType,
+        object_type: &StructTag,
+    ) -> Result<&'a ChangedObject> {
+        match objects.of_type(object_type) {
+            [object] => Ok(object),
+            others => Err(Error::UnexpectedObjectCount {
+                digest: self.digest.to_string(),
+                change,
+                object_type: object_type.to_string(),
+                count: others.len(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<SuiTransactionBlockResponse> for CallEffects {
+    type Error = Error;
+
+    fn try_from(response: SuiTransactionBlockResponse) -> Result<Self> {
+        let digest = response.digest;
+        let effects = response.effects.ok_or(Error::MissingEffects {
+            digest: digest.to_string(),
+        })?;
+
+        let mut created = ObjectsByType::default();
+        let mut mutated = ObjectsByType::default();
+        let mut transferred = ObjectsByType::default();
+        let mut deleted = ObjectsByType::default();
+        for change in response.object_changes.unwrap_or_default() {
+            match change {
+                ObjectChange::Created {
+                    object_id,
+                    object_type,
+                    owner,
+                    ..
+                } => created.push(ChangedObject {
+                    object_id,
+                    object_type,
+                    owner: Some(owner),
+                }),
+                ObjectChange::Mutated {
+                    object_id,
+                    object_type,
+                    owner,
+                    ..
+                } => mutated.push(ChangedObject {
+                    object_id,
+                    object_type,
+                    owner: Some(owner),
+                }),
+                ObjectChange::Transferred {
+                    object_id,
+                    object_type,
+                    recipient,
+                    ..
+                } => transferred.push(ChangedObject {
+                    object_id,
+                    object_type,
+                    owner: Some(recipient),
+                }),
+                ObjectChange::Deleted {
+                    object_id,
+                    object_type,
+                    ..
+                } => deleted.push(ChangedObject {
+                    object_id,
+                    object_type,
+                    owner: None,
+                }),
+                _ => (),
+            }
+        }
+
+        Ok(CallEffects {
+            digest,
+            status: effects.status().clone(),
+            created,
+            mutated,
+            transferred,
+            deleted,
+            gas_used: effects.gas_cost_summary().clone(),
+            events: response
+                .events
+                .map(|events| events.data)
+                .unwrap_or_default(),
+        })
+    }
+}
+// endregion: --- Call Effects
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use move_core_types::language_storage::StructTag;
+    use sui_json_rpc_types::SuiExecutionStatus;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+    use sui_types::digests::TransactionDigest;
+    use sui_types::gas::GasCostSummary;
+    use sui_types::object::Owner;
+
+    use super::{CallEffects, ChangedObject, Error};
+    use crate::sui_call::{game_type, BOT_OBJECT_NAME, PET_OBJECT_NAME};
+
+    fn package() -> ObjectID {
+        ObjectID::from_single_byte(0xab)
+    }
+
+    fn changed(id: u8, object_type: StructTag) -> ChangedObject {
+        ChangedObject {
+            object_id: ObjectID::from_single_byte(id),
+            object_type,
+            owner: Some(Owner::AddressOwner(SuiAddress::ZERO)),
+        }
+    }
+
+    fn effects(created: Vec<ChangedObject>) -> CallEffects {
+        CallEffects {
+            digest: TransactionDigest::new([1; 32]),
+            status: SuiExecutionStatus::Success,
+            created: created.into_iter().collect(),
+            mutated: Default::default(),
+            transferred: Default::default(),
+            deleted: Default::default(),
+            gas_used: GasCostSummary::default(),
+            events: vec![],
+        }
+    }
+
+    #[test]
+    fn test_created_object_by_type_ok() {
+        let bot_type = game_type(package(), BOT_OBJECT_NAME);
+        let effects = effects(vec![
+            changed(1, game_type(package(), PET_OBJECT_NAME)),
+            changed(
+                2,
+                game_type(ObjectID::from_single_byte(0xcd), BOT_OBJECT_NAME),
+            ),
+            changed(3, bot_type.clone()),
+        ]);
+
+        let bot = effects.created_object(&bot_type).unwrap();
+
+        assert_eq!(bot.object_id, ObjectID::from_single_byte(3));
+        assert_eq!(bot.owner_address(), Some(SuiAddress::ZERO));
+    }
+
+    #[test]
+    fn test_created_object_ambiguous_err() {
+        let bot_type = game_type(package(), BOT_OBJECT_NAME);
+        let effects = effects(vec![
+            changed(1, bot_type.clone()),
+            changed(2, bot_type.clone()),
+        ]);
+
+        let res = effects.created_object(&bot_type);
+
+        assert!(matches!(
+            res,
+            Err(Error::UnexpectedObjectCount {
+                change: "created",
+                count: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_mutated_object_missing_err() {
+        let effects = effects(vec![changed(1, game_type(package(), PET_OBJECT_NAME))]);
+
+        let res = effects.mutated_object(&game_type(package(), PET_OBJECT_NAME));
+
+        assert!(matches!(
+            res,
+            Err(Error::UnexpectedObjectCount { count: 0, .. })
+        ));
+    }
+}
+// endregion: --- Tests
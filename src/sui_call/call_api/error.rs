@@ -1,22 +1,72 @@
-use sui_sdk::error::Error as SuiError;
-use sui_sdk::types::base_types::ObjectIDParseError;
+use serde::Serialize;
 
-use crate::models;
+use super::abort::GameError;
 
 pub type Result<T> = core::result::Result<T, Error>;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, strum_macros::AsRefStr)]
+#[serde(tag = "type", content = "data")]
 pub enum Error {
-    TransactionFail,
-}
+    // -- Build
+    BadArgument {
+        index: usize,
+        value: String,
+    },
+    BadSigner(String),
+    BuildTransaction(String),
+
+    // -- Dry run
+    DryRun(String),
+    DryRunFail {
+        function: &'static str,
+        reason: String,
+    },
+    GasBudgetExceeded {
+        function: &'static str,
+        budget: u64,
+        ceiling: u64,
+    },
+
+    // -- Gas pool
+    GasPool(String),
+    GasCoinUnavailable {
+        owner: String,
+    },
+
+    // -- Keystore
+    Keystore(String),
+    KeyNotFound(String),
 
-// region:    --- Froms
-// impl From<SuiError> for Error {
-//     fn from(val: SuiError) -> Self {
-//         Self::Sui(val)
-//     }
-// }
-// endregion: --- Froms
+    // -- Sign
+    Signing {
+        signer: String,
+        reason: String,
+    },
+
+    // -- Execute
+    Execution(String),
+    MissingEffects {
+        digest: String,
+    },
+    TransactionFail {
+        digest: String,
+        error: String,
+    },
+    /// A dry run or an execution failure found in the `AbortCatalog`.
+    GameRejected {
+        function: &'static str,
+        game_error: GameError,
+    },
+
+    // -- Effects
+    /// Not exactly one object of `object_type` in the `change` (created, mutated...) objects.
+    UnexpectedObjectCount {
+        digest: String,
+        change: &'static str,
+        object_type: String,
+        count: usize,
+    },
+}
 
 // region:    --- Error Boilerplate
 impl core::fmt::Display for Error {
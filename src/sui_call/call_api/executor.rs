@@ -0,0 +1,284 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use move_core_types::language_storage::StructTag;
+use serde_json::Value;
+use sui_json_rpc_types::SuiExecutionStatus;
+use sui_sdk::json::SuiJsonValue;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::transaction::TransactionDataAPI;
+use tracing::debug;
+
+use super::effects::CallEffects;
+use super::error::{Error, Result};
+use super::gas::{budget_from_dry_run, readable_failure};
+use super::gas_pool::{GasLease, GasPool};
+use super::keystore::GameKeystore;
+use super::ptb::Ptb;
+use crate::get_config;
+use crate::sui_call::gateway::SuiGateway;
+use crate::sui_call::{game_type, MODULE_NAME};
+
+// region:    --- Move Call
+
+/// A call to a function of the game package.
+///
+/// Game actions only declare the function and its arguments, the executor
+/// takes care of building, signing and executing the transaction.
+#[derive(Debug, Clone)]
+pub struct MoveCall {
+    pub module: &'static str,
+    pub function: &'static str,
+    pub args: Vec<MoveArg>,
+}
+
+#[derive(Debug, Clone)]
+pub enum MoveArg {
+    Object(String),
+    Address(SuiAddress),
+    U8(u8),
+    U64(u64),
+}
+
+impl MoveCall {
+    pub fn new(function: &'static str) -> Self {
+        MoveCall {
+            module: MODULE_NAME,
+            function,
+            args: Vec::new(),
+        }
+    }
+
+    pub fn object(mut self, id: impl ToString) -> Self {
+        self.args.push(MoveArg::Object(id.to_string()));
+        self
+    }
+
+    pub fn address(mut self, address: SuiAddress) -> Self {
+        self.args.push(MoveArg::Address(address));
+        self
+    }
+
+    pub fn u8(mut self, value: u8) -> Self {
+        self.args.push(MoveArg::U8(value));
+        self
+    }
+
+    pub fn u64(mut self, value: u64) -> Self {
+        self.args.push(MoveArg::U64(value));
+        self
+    }
+
+    pub(crate) fn sui_json_args(&self) -> Result<Vec<SuiJsonValue>> {
+        self.args
+            .iter()
+            .enumerate()
+            .map(|(index, arg)| {
+                let bad_argument = || Error::BadArgument {
+                    index,
+                    value: format!("{arg:?}"),
+                };
+
+                match arg {
+                    MoveArg::Object(id) => ObjectID::from_str(id)
+                        .map(SuiJsonValue::from_object_id)
+                        .map_err(|_| bad_argument()),
+                    MoveArg::Address(address) => {
+                        SuiJsonValue::new(Value::String(address.to_string()))
+                            .map_err(|_| bad_argument())
+                    }
+                    MoveArg::U8(value) => {
+                        SuiJsonValue::new(Value::from(*value)).map_err(|_| bad_argument())
+                    }
+                    // u64 goes as a string, json numbers lose precision above 2^53.
+                    MoveArg::U64(value) => SuiJsonValue::new(Value::String(value.to_string()))
+                        .map_err(|_| bad_argument()),
+                }
+            })
+            .collect()
+    }
+}
+// endregion: --- Move Call
+
+// region:    --- Executor
+
+/// Builds, signs and executes the game move calls and blocks.
+///
+/// Cheap to clone, the gateway and the keystore are shared between the
+/// clones so keys added at registration are visible to the discord commands.
+///
+/// The transactions of the gas pool owner pay with a coin leased from the pool.
+#[derive(Clone)]
+pub struct SuiExecutor {
+    gateway: Arc<dyn SuiGateway>,
+    package_id: ObjectID,
+    keystore: Arc<GameKeystore>,
+    gas_pool: Option<Arc<GasPool>>,
+}
+
+impl SuiExecutor {
+    pub fn new(gateway: Arc<dyn SuiGateway>, package_id: ObjectID, keystore: GameKeystore) -> Self {
+        SuiExecutor {
+            gateway,
+            package_id,
+            keystore: Arc::new(keystore),
+            gas_pool: None,
+        }
+    }
+
+    pub fn with_gas_pool(mut self, gas_pool: Arc<GasPool>) -> Self {
+        self.gas_pool = Some(gas_pool);
+        self
+    }
+
+    pub fn gateway(&self) -> &Arc<dyn SuiGateway> {
+        &self.gateway
+    }
+
+    pub fn package_id(&self) -> &ObjectID {
+        &self.package_id
+    }
+
+    pub fn keystore(&self) -> &GameKeystore {
+        &self.keystore
+    }
+
+    pub fn gas_pool(&self) -> Option<&Arc<GasPool>> {
+        self.gas_pool.as_ref()
+    }
+
+    /// The move type of the game struct `name` (e.g. `Bot`), to look up in the effects.
+    pub fn game_type(&self, name: &str) -> StructTag {
+        game_type(self.package_id, name)
+    }
+
+    pub async fn execute(&self, signer: SuiAddress, call: MoveCall) -> Result<CallEffects> {
+        debug!(
+            "{:<12} - {}::{} - signer: {signer}",
+            "MOVE_CALL", call.module, call.function
+        );
+
+        self.execute_ptb(signer, Ptb::from(call)).await
+    }
+
+    /// Execute the commands of `ptb` in one transaction.
+    pub async fn execute_ptb(&self, signer: SuiAddress, ptb: Ptb) -> Result<CallEffects> {
+        let lease = match &self.gas_pool {
+            Some(gas_pool) if gas_pool.owner() == signer => Some(gas_pool.lease().await?),
+            _ => None,
+        };
+
+        self.execute_ptb_with_gas(signer, ptb, lease).await
+    }
+
+    /// Execute `ptb` paying with the coin of `lease`, given back once the effects are in.
+    pub async fn execute_ptb_with_gas(
+        &self,
+        signer: SuiAddress,
+        ptb: Ptb,
+        lease: Option<GasLease>,
+    ) -> Result<CallEffects> {
+        let config = get_config();
+        let gas_coin = lease.as_ref().map(GasLease::coin);
+        let ceiling = config.gas_ceiling(ptb.name);
+
+        // -- Dry-run to surface aborts before signing and to size the gas budget.
+        let mut prepared = self
+            .gateway
+            .prepare_ptb(signer, self.package_id, &ptb, ceiling, gas_coin)
+            .await?;
+
+        if let SuiExecutionStatus::Failure { error } = &prepared.dry_run_status {
+            return Err(
+                game_rejection(ptb.name, error).unwrap_or_else(|| Error::DryRunFail {
+                    function: ptb.name,
+                    reason: readable_failure(error),
+                }),
+            );
+        }
+
+        let budget = budget_from_dry_run(&prepared.dry_run_cost, config.GAS_BUDGET_MARGIN_PCT);
+        if budget > ceiling {
+            return Err(Error::GasBudgetExceeded {
+                function: ptb.name,
+                budget,
+                ceiling,
+            });
+        }
+        prepared.tx_data.gas_data_mut().budget = budget;
+
+        debug!(
+            "{:<12} - {} ({} commands) - budget: {budget}",
+            "GAS_BUDGET",
+            ptb.name,
+            ptb.commands.len()
+        );
+
+        let effects = self
+            .gateway
+            .sign_and_execute(prepared, &self.keystore)
+            .await?;
+
+        if let SuiExecutionStatus::Failure { error } = &effects.status {
+            return Err(game_rejection(ptb.name, error).unwrap_or_else(|| {
+                Error::TransactionFail {
+                    digest: effects.digest.to_string(),
+                    error: readable_failure(error),
+                }
+            }));
+        }
+
+        Ok(effects)
+    }
+}
+
+/// The game error of a failure status, from the `ABORT_CATALOG`.
+fn game_rejection(function: &'static str, error: &str) -> Option<Error> {
+    let game_error = get_config().ABORT_CATALOG.decode(error)?;
+    debug!(
+        "{:<12} - {function} - {game_error:?} - {error}",
+        "GAME_REJECT"
+    );
+
+    Some(Error::GameRejected {
+        function,
+        game_error,
+    })
+}
+// endregion: --- Executor
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use sui_types::base_types::SuiAddress;
+
+    use super::{Error, MoveArg, MoveCall};
+
+    #[test]
+    fn test_move_call_args_ok() {
+        let call = MoveCall::new("send_bot")
+            .object("0x6")
+            .address(SuiAddress::ZERO)
+            .u8(100)
+            .u64(u64::MAX);
+
+        let args = call.sui_json_args().unwrap();
+
+        assert_eq!(args.len(), 4);
+        assert_eq!(args[2].to_json_value(), serde_json::json!(100));
+        assert_eq!(
+            args[3].to_json_value(),
+            serde_json::json!(u64::MAX.to_string())
+        );
+    }
+
+    #[test]
+    fn test_move_call_args_bad_object() {
+        let call = MoveCall::new("huntbot").object("0x6").object("not-an-id");
+
+        let res = call.sui_json_args();
+
+        assert!(matches!(res, Err(Error::BadArgument { index: 1, .. })));
+    }
+}
+// endregion:    --- Tests
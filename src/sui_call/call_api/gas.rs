@@ -0,0 +1,119 @@
+use sui_types::gas::GasCostSummary;
+
+/// Gas budget covering the dry-run cost plus `margin_pct` percent.
+///
+/// The storage rebate is not subtracted, the budget has to cover the
+/// computation and storage costs before the rebate is paid back.
+pub fn budget_from_dry_run(cost: &GasCostSummary, margin_pct: u64) -> u64 {
+    let estimated = cost.computation_cost.saturating_add(cost.storage_cost);
+
+    estimated.saturating_add(estimated.saturating_mul(margin_pct) / 100)
+}
+
+// region:    --- Move Abort
+
+/// A move abort pulled out of an execution status error, e.g.
+/// `MoveAbort(MoveLocation { module: ModuleId { address: .., name: Identifier("we_pet_game") }, function: 3, instruction: 12, function_name: Some("hunt") }, 2) in command 0`
+#[derive(Debug, PartialEq)]
+pub struct MoveAbort {
+    pub module: String,
+    pub function: Option<String>,
+    pub code: u64,
+}
+
+impl MoveAbort {
+    pub fn parse(error: &str) -> Option<Self> {
+        let error = error.strip_prefix("MoveAbort(")?;
+
+        let module = quoted_after(error, "name: Identifier(")?;
+        let function = quoted_after(error, "function_name: Some(");
+
+        // The abort code follows the closing brace of the location.
+        let (_, code) = error.rsplit_once("}, ")?;
+        let code = code
+            .split(|c: char| !c.is_ascii_digit())
+            .next()?
+            .parse()
+            .ok()?;
+
+        Some(MoveAbort {
+            module,
+            function,
+            code,
+        })
+    }
+}
+
+impl core::fmt::Display for MoveAbort {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        match &self.function {
+            Some(function) => write!(
+                fmt,
+                "{}::{} aborted with code {}",
+                self.module, function, self.code
+            ),
+            None => write!(fmt, "{} aborted with code {}", self.module, self.code),
+        }
+    }
+}
+
+fn quoted_after(haystack: &str, prefix: &str) -> Option<String> {
+    let (_, rest) = haystack.split_once(prefix)?;
+    let rest = rest.strip_prefix('"')?;
+    let (value, _) = rest.split_once('"')?;
+
+    Some(value.to_string())
+}
+
+/// Readable reason for a failed execution status.
+pub fn readable_failure(error: &str) -> String {
+    MoveAbort::parse(error)
+        .map(|abort| abort.to_string())
+        .unwrap_or_else(|| error.to_string())
+}
+// endregion: --- Move Abort
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use sui_types::gas::GasCostSummary;
+
+    use super::{budget_from_dry_run, readable_failure, MoveAbort};
+
+    #[test]
+    fn test_budget_from_dry_run_margin() {
+        let cost = GasCostSummary::new(1_000_000, 2_000_000, 1_500_000, 0);
+
+        assert_eq!(budget_from_dry_run(&cost, 0), 3_000_000);
+        assert_eq!(budget_from_dry_run(&cost, 20), 3_600_000);
+    }
+
+    #[test]
+    fn test_move_abort_parse_ok() {
+        let error = r#"MoveAbort(MoveLocation { module: ModuleId { address: 0000000000000000000000000000000000000000000000000000000000000abc, name: Identifier("we_pet_game") }, function: 3, instruction: 12, function_name: Some("hunt") }, 2) in command 0"#;
+
+        let abort = MoveAbort::parse(error).unwrap();
+
+        assert_eq!(
+            abort,
+            MoveAbort {
+                module: "we_pet_game".to_string(),
+                function: Some("hunt".to_string()),
+                code: 2,
+            }
+        );
+        assert_eq!(
+            readable_failure(error),
+            "we_pet_game::hunt aborted with code 2"
+        );
+    }
+
+    #[test]
+    fn test_readable_failure_not_an_abort() {
+        let error = "InsufficientGas";
+
+        assert_eq!(MoveAbort::parse(error), None);
+        assert_eq!(readable_failure(error), "InsufficientGas");
+    }
+}
+// endregion:    --- Tests
@@ -0,0 +1,368 @@
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use sui_types::base_types::{ObjectID, SuiAddress};
+use tokio::sync::Notify;
+use tracing::{debug, error, info};
+
+use super::error::{Error, Result};
+use super::executor::SuiExecutor;
+use super::ptb::Ptb;
+
+/// The gas coins of one signer (the admin), one per transaction in flight.
+///
+/// Two transactions paying with the same coin at the same time lock it
+/// (equivocation), so each transaction leases its own coin and gives it back
+/// once its effects are in. `rebalance` splits a big coin when the pool has
+/// fewer than `size` coins of at least `min_balance`, it runs once before the
+/// first lease.
+pub struct GasPool {
+    owner: SuiAddress,
+    size: usize,
+    min_balance: u64,
+    lease_timeout: Duration,
+    coins: Mutex<PoolCoins>,
+    returned: Notify,
+}
+
+#[derive(Default)]
+struct PoolCoins {
+    free: VecDeque<ObjectID>,
+    leased: HashSet<ObjectID>,
+    /// The coins holding `min_balance` at the last refresh.
+    usable: HashSet<ObjectID>,
+}
+
+/// A coin taken from its pool, given back on drop.
+pub struct GasLease {
+    pool: Arc<GasPool>,
+    coin: ObjectID,
+}
+
+impl GasLease {
+    pub fn coin(&self) -> ObjectID {
+        self.coin
+    }
+}
+
+impl Drop for GasLease {
+    fn drop(&mut self) {
+        self.pool.give_back(self.coin);
+    }
+}
+
+impl GasPool {
+    pub fn new(owner: SuiAddress, size: usize, min_balance: u64, lease_timeout: Duration) -> Self {
+        GasPool {
+            owner,
+            size,
+            min_balance,
+            lease_timeout,
+            coins: Mutex::new(PoolCoins::default()),
+            returned: Notify::new(),
+        }
+    }
+
+    pub fn owner(&self) -> SuiAddress {
+        self.owner
+    }
+
+    /// Free coins, not leased.
+    pub fn available(&self) -> usize {
+        self.coins.lock().unwrap().free.len()
+    }
+
+    /// Lease a free coin, waiting for one to come back (or to be split) when
+    /// none is free.
+    pub async fn lease(self: &Arc<Self>) -> Result<GasLease> {
+        let deadline = tokio::time::Instant::now() + self.lease_timeout;
+
+        loop {
+            {
+                let mut coins = self.coins.lock().unwrap();
+                if let Some(coin) = coins.free.pop_front() {
+                    coins.leased.insert(coin);
+                    return Ok(self.leased(coin));
+                }
+            }
+
+            if tokio::time::timeout_at(deadline, self.returned.notified())
+                .await
+                .is_err()
+            {
+                return Err(Error::GasCoinUnavailable {
+                    owner: self.owner.to_string(),
+                });
+            }
+        }
+    }
+
+    /// Lease `coin` if it is free.
+    fn take(self: &Arc<Self>, coin: ObjectID) -> Option<GasLease> {
+        let mut coins = self.coins.lock().unwrap();
+        let index = coins.free.iter().position(|free| *free == coin)?;
+        coins.free.remove(index);
+        coins.leased.insert(coin);
+
+        Some(self.leased(coin))
+    }
+
+    fn leased(self: &Arc<Self>, coin: ObjectID) -> GasLease {
+        GasLease {
+            pool: self.clone(),
+            coin,
+        }
+    }
+
+    /// A coin no longer usable at the last refresh (spent below `min_balance`)
+    /// is dropped from the pool.
+    fn give_back(&self, coin: ObjectID) {
+        let mut coins = self.coins.lock().unwrap();
+        if coins.leased.remove(&coin) && coins.usable.contains(&coin) {
+            coins.free.push_back(coin);
+            self.returned.notify_one();
+        }
+    }
+
+    /// The free coins become `usable`, the leased ones come back on their own.
+    fn refresh(&self, usable: &[ObjectID]) {
+        let mut coins = self.coins.lock().unwrap();
+        let leased = coins.leased.clone();
+        coins.free = usable
+            .iter()
+            .filter(|coin| !leased.contains(coin))
+            .copied()
+            .collect();
+        coins.usable = usable.iter().copied().collect();
+
+        for _ in 0..coins.free.len() {
+            self.returned.notify_one();
+        }
+    }
+
+    /// Pick up the coins of the owner, then split the biggest free one when
+    /// fewer than `size` hold `min_balance`.
+    pub async fn rebalance(self: &Arc<Self>, executor: &SuiExecutor) -> Result<()> {
+        let mut coins = executor
+            .gateway()
+            .get_coins(self.owner)
+            .await
+            .map_err(|e| Error::GasPool(e.to_string()))?;
+        coins.retain(|coin| coin.balance >= self.min_balance);
+        coins.sort_by_key(|coin| std::cmp::Reverse(coin.balance));
+
+        let usable: Vec<ObjectID> = coins.iter().map(|coin| coin.coin_object_id).collect();
+        self.refresh(&usable);
+
+        let missing = self.size.saturating_sub(coins.len());
+        if missing == 0 {
+            return Ok(());
+        }
+
+        // -- The biggest free coin keeps a share, each new coin gets one.
+        let Some((biggest, lease)) = coins
+            .iter()
+            .find_map(|coin| self.take(coin.coin_object_id).map(|lease| (coin, lease)))
+        else {
+            return Ok(());
+        };
+        let splits = missing.min((biggest.balance / self.min_balance).saturating_sub(1) as usize);
+        if splits == 0 {
+            debug!(
+                "{:<12} - {missing} coin(s) missing, balance too low to split",
+                "GAS_POOL"
+            );
+            return Ok(());
+        }
+        let share = biggest.balance / (splits as u64 + 1);
+
+        let ptb = (0..splits).fold(Ptb::new("gas_pool_split"), |ptb, _| {
+            ptb.transfer_sui(self.owner, share)
+        });
+        executor
+            .execute_ptb_with_gas(self.owner, ptb, Some(lease))
+            .await?;
+        info!(
+            "{:<12} - split {} into {splits} coin(s) of {share}",
+            "GAS_POOL", biggest.coin_object_id
+        );
+
+        // -- Pick up the new coins.
+        let coins = executor
+            .gateway()
+            .get_coins(self.owner)
+            .await
+            .map_err(|e| Error::GasPool(e.to_string()))?;
+        let usable: Vec<ObjectID> = coins
+            .iter()
+            .filter(|coin| coin.balance >= self.min_balance)
+            .map(|coin| coin.coin_object_id)
+            .collect();
+        self.refresh(&usable);
+
+        Ok(())
+    }
+
+    /// Rebalance forever, every `interval`, after the first `rebalance`.
+    pub async fn run(self: Arc<Self>, executor: SuiExecutor, interval: Duration) {
+        loop {
+            tokio::time::sleep(interval).await;
+
+            if let Err(e) = self.rebalance(&executor).await {
+                error!("{:<12} - {e:?}", "GAS_POOL");
+            }
+        }
+    }
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use dotenvy::dotenv;
+    use sui_json_rpc_types::Coin;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+
+    use super::GasPool;
+    use crate::_dev_init;
+    use crate::get_config;
+    use crate::sui_call::call_api::onboard::{onboard, Onboarding};
+    use crate::sui_call::call_api::Error;
+    use crate::sui_call::gateway::{SuiGateway, FAKE_FAUCET_AMOUNT};
+
+    fn pool_of(coins: &[u8], lease_timeout: Duration) -> Arc<GasPool> {
+        let pool = Arc::new(GasPool::new(
+            SuiAddress::ZERO,
+            coins.len(),
+            1,
+            lease_timeout,
+        ));
+        let coins: Vec<ObjectID> = coins
+            .iter()
+            .map(|c| ObjectID::from_single_byte(*c))
+            .collect();
+        pool.refresh(&coins);
+
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_lease_distinct_coins_and_give_back() {
+        let pool = pool_of(&[1, 2], Duration::from_secs(1));
+
+        let first = pool.lease().await.unwrap();
+        let second = pool.lease().await.unwrap();
+        assert_ne!(first.coin(), second.coin());
+        assert_eq!(pool.available(), 0);
+
+        drop(first);
+        assert_eq!(pool.available(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_lease_waits_for_a_coin() {
+        let pool = pool_of(&[1], Duration::from_secs(5));
+        let lease = pool.lease().await.unwrap();
+
+        let waiting = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.lease().await.map(|lease| lease.coin()) }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        drop(lease);
+
+        let coin = waiting.await.unwrap().unwrap();
+        assert_eq!(coin, ObjectID::from_single_byte(1));
+    }
+
+    #[tokio::test]
+    async fn test_lease_timeout_err() {
+        let pool = pool_of(&[1], Duration::from_millis(20));
+        let _lease = pool.lease().await.unwrap();
+
+        let res = pool.lease().await;
+
+        assert!(matches!(res, Err(Error::GasCoinUnavailable { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_lease_empty_pool_err() {
+        let pool = pool_of(&[], Duration::from_millis(20));
+
+        let res = pool.lease().await;
+
+        assert!(matches!(res, Err(Error::GasCoinUnavailable { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_give_back_spent_coin_dropped() {
+        let pool = pool_of(&[1, 2], Duration::from_secs(1));
+        let lease = pool.lease().await.unwrap();
+
+        // -- Coin 1 fell below the min balance while leased.
+        pool.refresh(&[ObjectID::from_single_byte(2)]);
+        drop(lease);
+
+        assert_eq!(pool.available(), 1);
+        assert_eq!(
+            pool.lease().await.unwrap().coin(),
+            ObjectID::from_single_byte(2)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rebalance_then_parallel_onboarding() {
+        dotenv().ok();
+
+        let (sui, executor) = _dev_init::init_sui_for_test();
+        let admin = SuiAddress::from_str(&get_config().SUI_CLIENT_ADDRESS).unwrap();
+        let pool = Arc::new(GasPool::new(
+            admin,
+            4,
+            FAKE_FAUCET_AMOUNT,
+            Duration::from_secs(1),
+        ));
+        let executor = executor.with_gas_pool(pool.clone());
+
+        // -- The one admin coin is split in 4.
+        pool.rebalance(&executor).await.unwrap();
+        assert_eq!(pool.available(), 4);
+
+        // -- Each onboarding pays with its own coin, the top-up comes out of it.
+        let onboarding = Onboarding {
+            profile: true,
+            bot: Some((50, 5)),
+            top_up: Some(1_000),
+        };
+        let balances = |coins: Vec<Coin>| -> HashMap<ObjectID, u64> {
+            coins
+                .into_iter()
+                .map(|coin| (coin.coin_object_id, coin.balance))
+                .collect()
+        };
+        let before = balances(sui.get_coins(admin).await.unwrap());
+
+        let players: Vec<SuiAddress> = (1..=4)
+            .map(|i| SuiAddress::from(ObjectID::from_single_byte(0xe0 + i)))
+            .collect();
+        let onboarded = futures::future::join_all(
+            players
+                .iter()
+                .map(|player| onboard(&executor, *player, &onboarding)),
+        )
+        .await;
+        assert!(onboarded.iter().all(Result::is_ok));
+        assert_eq!(pool.available(), 4);
+
+        let after = balances(sui.get_coins(admin).await.unwrap());
+        assert!(before
+            .iter()
+            .all(|(coin, balance)| after[coin] == balance - 1_000));
+    }
+}
+// endregion: --- Tests
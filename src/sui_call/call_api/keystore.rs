@@ -0,0 +1,277 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use shared_crypto::intent::Intent;
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{Signature, SignatureScheme};
+use sui_types::transaction::TransactionData;
+use tokio::sync::RwLock;
+use tracing::debug;
+
+use super::error::{Error, Result};
+use crate::config::Config;
+use crate::ctx::Ctx;
+use crate::models::wallet::WalletBmc;
+use crate::models::{self, ModelManager};
+
+// region:    --- Backend
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeystoreBackend {
+    /// Keys live in a sui `FileBasedKeystore` file.
+    File,
+    /// Player keys are rebuilt from the `wallet` table, the admin key from config.
+    Db,
+}
+
+impl FromStr for KeystoreBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        match s {
+            "file" => Ok(KeystoreBackend::File),
+            "db" => Ok(KeystoreBackend::Db),
+            other => Err(format!("unknown keystore backend '{other}'")),
+        }
+    }
+}
+// endregion: --- Backend
+
+/// The keys the server signs with, shared by the discord handler and the routes.
+pub struct GameKeystore {
+    backend: KeystoreBackend,
+    keys: RwLock<Keystore>,
+    mm: Option<ModelManager>,
+}
+
+impl GameKeystore {
+    pub fn from_config(config: &Config, mm: ModelManager) -> Result<Self> {
+        match config.KEYSTORE_BACKEND {
+            KeystoreBackend::File => Self::file(&config.KEYSTORE_PATH),
+            KeystoreBackend::Db => {
+                let admin_phrase = config
+                    .KEYSTORE_ADMIN_PHRASE
+                    .as_deref()
+                    .ok_or(Error::Keystore("KEYSTORE_ADMIN_PHRASE missing".to_string()))?;
+
+                Self::db(mm, admin_phrase)
+            }
+        }
+    }
+
+    pub fn file(path: &PathBuf) -> Result<Self> {
+        let keystore = FileBasedKeystore::new(path).map_err(|e| Error::Keystore(e.to_string()))?;
+
+        Ok(GameKeystore {
+            backend: KeystoreBackend::File,
+            keys: RwLock::new(Keystore::from(keystore)),
+            mm: None,
+        })
+    }
+
+    pub fn db(mm: ModelManager, admin_phrase: &str) -> Result<Self> {
+        let mut keys = Keystore::from(InMemKeystore::default());
+        keys.import_from_mnemonic(admin_phrase, SignatureScheme::ED25519, None)
+            .map_err(|e| Error::Keystore(e.to_string()))?;
+
+        Ok(GameKeystore {
+            backend: KeystoreBackend::Db,
+            keys: RwLock::new(keys),
+            mm: Some(mm),
+        })
+    }
+
+    pub fn backend(&self) -> KeystoreBackend {
+        self.backend
+    }
+
+    /// Generate a new ED25519 key, returns its address, recovery phrase and scheme.
+    ///
+    /// The file backend persists the key itself, with the db backend the
+    /// caller stores the phrase in the `wallet` table.
+    pub async fn generate_key(&self) -> Result<(SuiAddress, String, SignatureScheme)> {
+        self.keys
+            .write()
+            .await
+            .generate_and_add_new_key(SignatureScheme::ED25519, None, None)
+            .map_err(|e| Error::Keystore(e.to_string()))
+    }
+
+    pub async fn sign(&self, signer: &SuiAddress, data: &TransactionData) -> Result<Signature> {
+        if self.backend == KeystoreBackend::Db && !self.has_key(signer).await {
+            self.load_from_db(signer).await?;
+        }
+
+        self.keys
+            .read()
+            .await
+            .sign_secure(signer, data, Intent::sui_transaction())
+            .map_err(|e| Error::Signing {
+                signer: signer.to_string(),
+                reason: e.to_string(),
+            })
+    }
+
+    async fn has_key(&self, address: &SuiAddress) -> bool {
+        self.keys.read().await.addresses().contains(address)
+    }
+
+    /// Rebuild the player key from its decrypted wallet phrase and keep it in memory.
+    async fn load_from_db(&self, address: &SuiAddress) -> Result<()> {
+        let Some(mm) = &self.mm else {
+            return Err(Error::KeyNotFound(address.to_string()));
+        };
+
+        let wallet = WalletBmc::get_for_sign(&Ctx::root_ctx(), mm, &address.to_string())
+            .await
+            .map_err(|e| match e {
+                models::Error::EntityNotFoundString { .. } => {
+                    Error::KeyNotFound(address.to_string())
+                }
+                e => Error::Keystore(e.to_string()),
+            })?;
+
+        let imported = self
+            .keys
+            .write()
+            .await
+            .import_from_mnemonic(&wallet.phrase, scheme_of(&wallet.sign_type), None)
+            .map_err(|e| Error::Keystore(e.to_string()))?;
+
+        debug!("{:<12} - wallet {} loaded", "KEYSTORE", wallet.id);
+
+        if &imported != address {
+            return Err(Error::KeyNotFound(address.to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+fn scheme_of(sign_type: &str) -> SignatureScheme {
+    match sign_type {
+        "secp256k1" => SignatureScheme::Secp256k1,
+        "secp256r1" => SignatureScheme::Secp256r1,
+        _ => SignatureScheme::ED25519,
+    }
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use dotenvy::dotenv;
+    use serial_test::serial;
+    use shared_crypto::intent::{Intent, IntentMessage};
+    use sui_types::base_types::{random_object_ref, SuiAddress};
+    use sui_types::crypto::{Signature, SignatureScheme, SuiSignature};
+    use sui_types::transaction::TransactionData;
+
+    use super::{scheme_of, GameKeystore, KeystoreBackend};
+    use crate::_dev_init;
+    use crate::ctx::Ctx;
+    use crate::models::wallet::{WalletBmc, WalletForCreate};
+    use crate::models::{UserBmc, UserForCreate};
+
+    fn transfer_tx(signer: SuiAddress) -> TransactionData {
+        TransactionData::new_transfer_sui(
+            SuiAddress::ZERO,
+            signer,
+            Some(1_000),
+            random_object_ref(),
+            10_000_000,
+            1_000,
+        )
+    }
+
+    fn assert_signed_by(signature: &Signature, signer: SuiAddress, tx_data: TransactionData) {
+        let intent_msg = IntentMessage::new(Intent::sui_transaction(), tx_data);
+
+        signature
+            .verify_secure(&intent_msg, signer, SignatureScheme::ED25519)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_keystore_backend_from_str() {
+        assert_eq!(
+            KeystoreBackend::from_str("file").unwrap(),
+            KeystoreBackend::File
+        );
+        assert_eq!(
+            KeystoreBackend::from_str("db").unwrap(),
+            KeystoreBackend::Db
+        );
+        assert!(KeystoreBackend::from_str("vault").is_err());
+    }
+
+    #[test]
+    fn test_scheme_of_sign_type() {
+        assert_eq!(scheme_of("ed25519"), SignatureScheme::ED25519);
+        assert_eq!(scheme_of(""), SignatureScheme::ED25519);
+        assert_eq!(scheme_of("secp256k1"), SignatureScheme::Secp256k1);
+    }
+
+    #[tokio::test]
+    async fn test_file_keystore_sign() {
+        let dir = tempfile::tempdir().unwrap();
+        let keystore = GameKeystore::file(&dir.path().join("test.keystore")).unwrap();
+        let (signer, _, _) = keystore.generate_key().await.unwrap();
+        let tx_data = transfer_tx(signer);
+
+        let signature = keystore.sign(&signer, &tx_data).await.unwrap();
+
+        assert_signed_by(&signature, signer, tx_data);
+    }
+
+    #[serial]
+    #[tokio::test]
+    async fn test_db_keystore_sign_loads_wallet() {
+        dotenv().ok();
+
+        let ctx = Ctx::root_ctx();
+        let mm = _dev_init::init_db_for_test().await;
+
+        // -- Two fresh phrases, one for the admin and one for the player wallet.
+        let dir = tempfile::tempdir().unwrap();
+        let generator = GameKeystore::file(&dir.path().join("test.keystore")).unwrap();
+        let (_, admin_phrase, _) = generator.generate_key().await.unwrap();
+        let (signer, phrase, _) = generator.generate_key().await.unwrap();
+
+        let user_c = UserForCreate {
+            username: Some("test_db_keystore_sign_loads_wallet".to_string()),
+            pwd: None,
+            email: None,
+        };
+        let user_id = UserBmc::create(&ctx, &mm, user_c).await.unwrap();
+        WalletBmc::create(
+            &ctx,
+            &mm,
+            WalletForCreate {
+                id: user_id,
+                pub_key: signer.to_string(),
+                sign_type: "ed25519".to_string(),
+                phrase,
+                last_faucet: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let keystore = GameKeystore::db(mm.clone(), &admin_phrase).unwrap();
+        assert!(!keystore.has_key(&signer).await);
+
+        // -- The player key is rebuilt from the decrypted phrase on first use.
+        let tx_data = transfer_tx(signer);
+        let signature = keystore.sign(&signer, &tx_data).await.unwrap();
+
+        assert!(keystore.has_key(&signer).await);
+        assert_signed_by(&signature, signer, tx_data);
+
+        WalletBmc::delete(&ctx, &mm, user_id).await.unwrap();
+        UserBmc::delete(&ctx, &mm, user_id).await.unwrap();
+    }
+}
+// endregion:    --- Tests
@@ -1,3 +1,20 @@
+mod abort;
 pub mod create_bot;
 pub mod create_profile;
+mod effects;
 mod error;
+mod executor;
+mod gas;
+mod gas_pool;
+mod keystore;
+pub mod onboard;
+mod ptb;
+
+pub use self::abort::{AbortCatalog, GameError};
+pub use self::effects::{CallEffects, ChangedObject, ObjectsByType};
+pub use self::error::{Error, Result};
+pub use self::executor::{MoveArg, MoveCall, SuiExecutor};
+pub use self::gas::MoveAbort;
+pub use self::gas_pool::{GasLease, GasPool};
+pub use self::keystore::{GameKeystore, KeystoreBackend};
+pub use self::ptb::{Ptb, PtbCommand};
@@ -0,0 +1,185 @@
+use std::str::FromStr;
+
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use tracing::debug;
+
+use super::error::{Error, Result};
+use super::executor::{MoveCall, SuiExecutor};
+use super::ptb::Ptb;
+use crate::get_config;
+use crate::sui_call::{BOT_OBJECT_NAME, HERO_OBJECT_NAME, PET_OBJECT_NAME};
+
+/// The parts of a new player sent in the onboarding block.
+#[derive(Debug, Clone, Default)]
+pub struct Onboarding {
+    /// `create_profile`, the hero and the first pet.
+    pub profile: bool,
+    /// `send_bot` with `(hp, strength)`.
+    pub bot: Option<(u8, u8)>,
+    /// MIST sent by the admin to the player.
+    pub top_up: Option<u64>,
+}
+
+impl Onboarding {
+    pub fn is_empty(&self) -> bool {
+        !self.profile && self.bot.is_none() && self.top_up.is_none()
+    }
+}
+
+/// The objects created by the onboarding block, `None` for a part not requested.
+#[derive(Debug)]
+pub struct Onboarded {
+    pub digest: TransactionDigest,
+    pub hero: Option<ObjectID>,
+    pub pet: Option<ObjectID>,
+    pub bot: Option<ObjectID>,
+}
+
+/// Create the profile, send the first bot and top up `player` in one transaction,
+/// signed by the admin.
+pub async fn onboard(
+    executor: &SuiExecutor,
+    player: SuiAddress,
+    onboarding: &Onboarding,
+) -> Result<Onboarded> {
+    let config = get_config();
+
+    if onboarding.is_empty() {
+        return Err(Error::BuildTransaction("nothing to onboard".to_string()));
+    }
+
+    let signer = SuiAddress::from_str(&config.SUI_CLIENT_ADDRESS)
+        .map_err(|e| Error::BadSigner(e.to_string()))?;
+
+    let mut ptb = Ptb::new("onboard");
+    if onboarding.profile {
+        ptb = ptb.move_call(
+            MoveCall::new("create_profile")
+                .object(&config.GAME_INFO_ID)
+                .object(&config.GAME_ADMIN_ID)
+                .address(player),
+        );
+    }
+    if let Some((hp, strength)) = onboarding.bot {
+        ptb = ptb.move_call(
+            MoveCall::new("send_bot")
+                .object(&config.GAME_INFO_ID)
+                .object(&config.GAME_ADMIN_ID)
+                .address(player)
+                .u8(hp)
+                .u8(strength),
+        );
+    }
+    if let Some(amount) = onboarding.top_up {
+        ptb = ptb.transfer_sui(player, amount);
+    }
+
+    let effects = executor.execute_ptb(signer, ptb).await?;
+
+    let created = |name| {
+        effects
+            .created_object(&executor.game_type(name))
+            .map(|obj| obj.object_id)
+    };
+
+    let onboarded = Onboarded {
+        digest: effects.digest,
+        hero: onboarding
+            .profile
+            .then(|| created(HERO_OBJECT_NAME))
+            .transpose()?,
+        pet: onboarding
+            .profile
+            .then(|| created(PET_OBJECT_NAME))
+            .transpose()?,
+        bot: onboarding
+            .bot
+            .map(|_| created(BOT_OBJECT_NAME))
+            .transpose()?,
+    };
+    debug!("{:<12} - {player} - {onboarded:?}", "ONBOARD");
+
+    Ok(onboarded)
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use dotenvy::dotenv;
+    use sui_json_rpc_types::SuiMoveValue;
+    use sui_types::base_types::SuiAddress;
+
+    use crate::_dev_init;
+    use crate::sui_call::call_api::{Error, GameError};
+    use crate::sui_call::gateway::SuiGateway;
+
+    use super::{onboard, Onboarding};
+
+    #[tokio::test]
+    async fn test_onboard_success() {
+        dotenv().ok();
+
+        let (sui, executor) = _dev_init::init_sui_for_test();
+        let player = SuiAddress::from_str(
+            "0x3f6a8b2c1d0e9f8a7b6c5d4e3f2a1b0c9d8e7f6a5b4c3d2e1f0a9b8c7d6e5f4a",
+        )
+        .unwrap();
+
+        let onboarding = Onboarding {
+            profile: true,
+            bot: Some((50, 5)),
+            top_up: Some(1_000),
+        };
+        let onboarded = onboard(&executor, player, &onboarding).await.unwrap();
+
+        assert!(onboarded.hero.is_some());
+        assert!(onboarded.pet.is_some());
+        let bot = sui.object_fields(&onboarded.bot.unwrap()).unwrap();
+        assert_eq!(bot["hp"], SuiMoveValue::String("50".to_string()));
+
+        let coins = sui.get_coins(player).await.unwrap();
+        assert_eq!(coins.iter().map(|coin| coin.balance).sum::<u64>(), 1_000);
+    }
+
+    #[tokio::test]
+    async fn test_onboard_top_up_too_big_changes_nothing() {
+        dotenv().ok();
+
+        let (sui, executor) = _dev_init::init_sui_for_test();
+        let player = SuiAddress::from_str(
+            "0x4a5b6c7d8e9f0a1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c1d2e3f4a5b",
+        )
+        .unwrap();
+
+        let onboarding = Onboarding {
+            profile: true,
+            bot: None,
+            top_up: Some(u64::MAX),
+        };
+        let res = onboard(&executor, player, &onboarding).await;
+
+        assert!(matches!(
+            res,
+            Err(Error::GameRejected {
+                function: "onboard",
+                game_error: GameError::NotEnoughCoin
+            })
+        ));
+        assert!(sui.get_coins(player).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_onboard_nothing_err() {
+        dotenv().ok();
+
+        let (_sui, executor) = _dev_init::init_sui_for_test();
+
+        let res = onboard(&executor, SuiAddress::ZERO, &Onboarding::default()).await;
+
+        assert!(matches!(res, Err(Error::BuildTransaction(_))));
+    }
+}
+// endregion:    --- Tests
@@ -0,0 +1,98 @@
+use sui_types::base_types::SuiAddress;
+
+use super::error::Result;
+use super::executor::MoveCall;
+
+/// Several commands run as one programmable transaction block,
+/// either all of them land or none does.
+///
+/// `name` stands for the block in the logs, the errors and the gas ceilings
+/// (`GAS_BUDGET_CEILINGS`), a single move call is named after its function.
+#[derive(Debug, Clone)]
+pub struct Ptb {
+    pub name: &'static str,
+    pub commands: Vec<PtbCommand>,
+}
+
+#[derive(Debug, Clone)]
+pub enum PtbCommand {
+    Call(MoveCall),
+    /// SUI split from the gas coin and sent to `recipient`.
+    TransferSui {
+        recipient: SuiAddress,
+        amount: u64,
+    },
+}
+
+impl Ptb {
+    pub fn new(name: &'static str) -> Self {
+        Ptb {
+            name,
+            commands: Vec::new(),
+        }
+    }
+
+    pub fn move_call(mut self, call: MoveCall) -> Self {
+        self.commands.push(PtbCommand::Call(call));
+        self
+    }
+
+    pub fn transfer_sui(mut self, recipient: SuiAddress, amount: u64) -> Self {
+        self.commands
+            .push(PtbCommand::TransferSui { recipient, amount });
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Check the arguments of every move call, before building anything.
+    pub(crate) fn check_args(&self) -> Result<()> {
+        for command in &self.commands {
+            if let PtbCommand::Call(call) = command {
+                call.sui_json_args()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<MoveCall> for Ptb {
+    fn from(call: MoveCall) -> Self {
+        Ptb::new(call.function).move_call(call)
+    }
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use sui_types::base_types::SuiAddress;
+
+    use super::{Ptb, PtbCommand};
+    use crate::sui_call::call_api::{Error, MoveCall};
+
+    #[test]
+    fn test_ptb_from_move_call() {
+        let ptb = Ptb::from(MoveCall::new("send_bot").object("0x6"));
+
+        assert_eq!(ptb.name, "send_bot");
+        assert!(
+            matches!(&ptb.commands[..], [PtbCommand::Call(call)] if call.function == "send_bot")
+        );
+    }
+
+    #[test]
+    fn test_ptb_check_args_bad_object() {
+        let ptb = Ptb::new("onboard")
+            .transfer_sui(SuiAddress::ZERO, 1)
+            .move_call(MoveCall::new("create_profile").object("0x6"))
+            .move_call(MoveCall::new("send_bot").object("not-an-id"));
+
+        let res = ptb.check_args();
+
+        assert!(matches!(res, Err(Error::BadArgument { index: 0, .. })));
+    }
+}
+// endregion:    --- Tests
@@ -0,0 +1,831 @@
+//! A deterministic, in-memory `we_pet_game`, for the tests that must not
+//! reach a network.
+//!
+//! Object ids and digests are counters, gas is reported but not charged
+//! and signatures are not checked. The move functions are simplified:
+//! - `create_profile` gives the player a `Hero` and a `Pet`.
+//! - `send_bot` gives the player a `Bot` and counts it on the `GameAdmin`.
+//! - `huntbot` always wins, the bot is deleted and the pet gains its hp as exp.
+//! - `hunt` takes the stake from the coin, the pet gains 1 exp per 0.01 SUI.
+//!
+//! A block runs its commands in order on a copy of the state, kept only
+//! when every command succeeds. SUI transfers come from the gas coin. When
+//! none is given, the richest coin of the signer that is not an object input
+//! of the block pays, like the sdk picks it.
+//!
+//! Objects not owned by the signer, or of the wrong type, abort with
+//! `ENOT_OWNER`, like a check in the move code would.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use move_core_types::language_storage::StructTag;
+use sui_json_rpc_types::{Coin, SuiExecutionStatus, SuiMoveValue};
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
+use sui_types::digests::{ObjectDigest, TransactionDigest};
+use sui_types::gas::GasCostSummary;
+use sui_types::object::Owner;
+use sui_types::transaction::{ProgrammableTransaction, TransactionData};
+use tracing::debug;
+
+use super::{FieldsPage, MoveFields, PreparedCall, SuiGateway};
+use crate::sui_call::call_api::{
+    self, CallEffects, ChangedObject, GameKeystore, MoveArg, MoveCall, ObjectsByType, Ptb,
+    PtbCommand,
+};
+use crate::sui_call::{
+    game_type, Result, ADMIN_OBJECT_NAME, BOT_OBJECT_NAME, HERO_OBJECT_NAME, MODULE_NAME,
+    PET_OBJECT_NAME,
+};
+
+const SUI_COIN_TYPE: &str = "0x2::sui::SUI";
+
+/// SUI sent by `request_faucet`, in MIST.
+pub const FAKE_FAUCET_AMOUNT: u64 = 1_000_000_000;
+
+// Same as the fullnode max page size.
+const FAKE_PAGE_SIZE: usize = 50;
+
+const FAKE_GAS_PRICE: u64 = 1_000;
+const FAKE_COMPUTATION_COST: u64 = 1_000_000;
+const FAKE_STORAGE_COST: u64 = 2_000_000; // per created object
+
+// Objects created by the fake move functions.
+const HERO_LEVEL: u64 = 1;
+const PET_HP: u64 = 100;
+const PET_STRENGTH: u64 = 10;
+const HUNT_EXP_UNIT: u64 = 10_000_000;
+
+// Abort codes of the fake move functions.
+const ENOT_OWNER: u64 = 1;
+const ENOT_ENOUGH_COIN: u64 = 2;
+
+// region:    --- State
+
+#[derive(Debug, Clone)]
+struct FakeObject {
+    owner: SuiAddress,
+    name: &'static str,
+    fields: MoveFields,
+}
+
+#[derive(Debug, Clone, Default)]
+struct FakeState {
+    next_id: u64,
+    tx_count: u64,
+    objects: BTreeMap<ObjectID, FakeObject>,
+    /// coin id -> (owner, balance)
+    coins: BTreeMap<ObjectID, (SuiAddress, u64)>,
+}
+
+/// The game objects changed by a block.
+#[derive(Debug, Default)]
+struct Changes {
+    created: Vec<ObjectID>,
+    mutated: Vec<ObjectID>,
+    deleted: Vec<(ObjectID, &'static str)>,
+}
+
+impl FakeState {
+    /// Ids start with `0xfa`, away from the system objects (`0x5`, `0x6`, ...).
+    fn new_id(&mut self) -> ObjectID {
+        self.next_id += 1;
+
+        let mut bytes = [0u8; ObjectID::LENGTH];
+        bytes[0] = 0xfa;
+        bytes[ObjectID::LENGTH - 8..].copy_from_slice(&self.next_id.to_be_bytes());
+
+        ObjectID::new(bytes)
+    }
+
+    fn new_digest(&mut self) -> TransactionDigest {
+        self.tx_count += 1;
+
+        let mut bytes = [0u8; 32];
+        bytes[24..].copy_from_slice(&self.tx_count.to_be_bytes());
+
+        TransactionDigest::new(bytes)
+    }
+
+    fn insert(&mut self, id: ObjectID, owner: SuiAddress, name: &'static str, fields: MoveFields) {
+        self.objects.insert(
+            id,
+            FakeObject {
+                owner,
+                name,
+                fields,
+            },
+        );
+    }
+}
+// endregion: --- State
+
+// region:    --- Fake Gateway
+
+/// The in-memory gateway, see the module doc for the simulated game.
+pub struct FakeGateway {
+    package_id: ObjectID,
+    state: Mutex<FakeState>,
+}
+
+impl FakeGateway {
+    pub fn new(package_id: ObjectID) -> Self {
+        FakeGateway {
+            package_id,
+            state: Mutex::new(FakeState::default()),
+        }
+    }
+
+    /// Deploy the game, the `GameAdmin` `game_admin` of the game `game_info`
+    /// goes to `admin`.
+    pub fn publish(&self, admin: SuiAddress, game_info: ObjectID, game_admin: ObjectID) {
+        let fields = MoveFields::from([
+            uid_field(game_admin),
+            number_field("bot_animal_created", 0),
+            address_field("game_id", game_info),
+        ]);
+
+        self.state
+            .lock()
+            .unwrap()
+            .insert(game_admin, admin, ADMIN_OBJECT_NAME, fields);
+    }
+
+    /// Give `owner` a SUI coin of `balance` MIST, returns the coin id.
+    pub fn add_coin(&self, owner: SuiAddress, balance: u64) -> ObjectID {
+        let mut state = self.state.lock().unwrap();
+
+        let id = state.new_id();
+        state.coins.insert(id, (owner, balance));
+
+        id
+    }
+
+    /// The current fields of the object `id`.
+    pub fn object_fields(&self, id: &ObjectID) -> Option<MoveFields> {
+        let state = self.state.lock().unwrap();
+
+        state.objects.get(id).map(|obj| obj.fields.clone())
+    }
+
+    /// The balance of the coin `id`.
+    pub fn coin_balance(&self, id: &ObjectID) -> Option<u64> {
+        let state = self.state.lock().unwrap();
+
+        state.coins.get(id).map(|(_, balance)| *balance)
+    }
+
+    fn is_type(&self, obj: &FakeObject, object_type: &StructTag) -> bool {
+        object_type.address == *self.package_id
+            && object_type.module.as_str() == MODULE_NAME
+            && object_type.name.as_str() == obj.name
+    }
+
+    fn struct_tag(&self, name: &'static str) -> StructTag {
+        game_type(self.package_id, name)
+    }
+
+    fn effects(
+        &self,
+        state: &FakeState,
+        digest: TransactionDigest,
+        status: SuiExecutionStatus,
+        changes: Changes,
+    ) -> CallEffects {
+        let changed = |ids: &[ObjectID]| -> ObjectsByType {
+            ids.iter()
+                .filter_map(|id| state.objects.get(id).map(|obj| (id, obj)))
+                .map(|(id, obj)| ChangedObject {
+                    object_id: *id,
+                    object_type: self.struct_tag(obj.name),
+                    owner: Some(Owner::AddressOwner(obj.owner)),
+                })
+                .collect()
+        };
+
+        CallEffects {
+            digest,
+            status,
+            created: changed(&changes.created),
+            mutated: changed(&changes.mutated),
+            transferred: ObjectsByType::default(),
+            deleted: changes
+                .deleted
+                .iter()
+                .map(|(id, name)| ChangedObject {
+                    object_id: *id,
+                    object_type: self.struct_tag(name),
+                    owner: None,
+                })
+                .collect(),
+            gas_used: gas_cost(&changes),
+            events: vec![],
+        }
+    }
+}
+
+#[async_trait]
+impl SuiGateway for FakeGateway {
+    async fn get_owned_objects(
+        &self,
+        owner: SuiAddress,
+        object_type: StructTag,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<FieldsPage> {
+        let state = self.state.lock().unwrap();
+
+        let mut matching = state.objects.iter().filter(|(id, obj)| {
+            obj.owner == owner
+                && self.is_type(obj, &object_type)
+                && cursor.map_or(true, |cursor| **id > cursor)
+        });
+
+        let page: Vec<_> = matching
+            .by_ref()
+            .take(limit.unwrap_or(FAKE_PAGE_SIZE))
+            .collect();
+        let has_next_page = matching.next().is_some();
+
+        Ok(FieldsPage {
+            next_cursor: page.last().map(|(id, _)| **id),
+            data: page
+                .into_iter()
+                .map(|(_, obj)| Some(obj.fields.clone()))
+                .collect(),
+            has_next_page,
+        })
+    }
+
+    async fn get_coins(&self, owner: SuiAddress) -> Result<Vec<Coin>> {
+        let state = self.state.lock().unwrap();
+
+        Ok(state
+            .coins
+            .iter()
+            .filter(|(_, (coin_owner, _))| *coin_owner == owner)
+            .map(|(id, (_, balance))| Coin {
+                coin_type: SUI_COIN_TYPE.to_string(),
+                coin_object_id: *id,
+                version: SequenceNumber::new(),
+                digest: ObjectDigest::new([0; 32]),
+                balance: *balance,
+                previous_transaction: TransactionDigest::new([0; 32]),
+            })
+            .collect())
+    }
+
+    async fn request_faucet(&self, address: SuiAddress) -> Result<()> {
+        self.add_coin(address, FAKE_FAUCET_AMOUNT);
+
+        Ok(())
+    }
+
+    async fn prepare_ptb(
+        &self,
+        signer: SuiAddress,
+        _package_id: ObjectID,
+        ptb: &Ptb,
+        gas_budget: u64,
+        gas_coin: Option<ObjectID>,
+    ) -> call_api::Result<PreparedCall> {
+        ptb.check_args()?;
+
+        // -- Run the block on a copy, a dry run changes nothing.
+        let mut scratch = self.state.lock().unwrap().clone();
+        let gas_coin = match gas_coin {
+            Some(coin) => {
+                if !matches!(scratch.coins.get(&coin), Some((owner, _)) if *owner == signer) {
+                    return Err(call_api::Error::BuildTransaction(format!(
+                        "gas coin {coin} not owned by {signer}"
+                    )));
+                }
+                coin
+            }
+            None => select_gas(&scratch, signer, ptb).ok_or_else(|| {
+                call_api::Error::BuildTransaction(format!(
+                    "no gas coin of {signer} outside the inputs of {}",
+                    ptb.name
+                ))
+            })?,
+        };
+        let (dry_run_status, dry_run_cost) =
+            match apply(&mut scratch, &self.package_id, signer, ptb, gas_coin) {
+                Ok(changes) => (SuiExecutionStatus::Success, gas_cost(&changes)),
+                Err(error) => (
+                    SuiExecutionStatus::Failure { error },
+                    gas_cost(&Changes::default()),
+                ),
+            };
+
+        Ok(PreparedCall {
+            signer,
+            ptb: ptb.clone(),
+            gas_coin: Some(gas_coin),
+            tx_data: placeholder_tx(signer, gas_budget, gas_coin),
+            dry_run_status,
+            dry_run_cost,
+        })
+    }
+
+    async fn sign_and_execute(
+        &self,
+        prepared: PreparedCall,
+        _keystore: &GameKeystore,
+    ) -> call_api::Result<CallEffects> {
+        let mut state = self.state.lock().unwrap();
+
+        // -- A failed transaction still gets a digest, but changes nothing.
+        let digest = state.new_digest();
+        let mut scratch = state.clone();
+
+        match apply(
+            &mut scratch,
+            &self.package_id,
+            prepared.signer,
+            &prepared.ptb,
+            prepared.gas_coin.unwrap_or(ObjectID::ZERO),
+        ) {
+            Ok(changes) => {
+                *state = scratch;
+                debug!("{:<12} - {} - {digest}", "FAKE_SUI", prepared.ptb.name);
+
+                Ok(self.effects(&state, digest, SuiExecutionStatus::Success, changes))
+            }
+            Err(error) => Ok(self.effects(
+                &state,
+                digest,
+                SuiExecutionStatus::Failure { error },
+                Changes::default(),
+            )),
+        }
+    }
+}
+// endregion: --- Fake Gateway
+
+// region:    --- Move Functions
+
+/// Run the commands of `ptb` on `state`, `Err` is the execution failure.
+fn apply(
+    state: &mut FakeState,
+    package_id: &ObjectID,
+    signer: SuiAddress,
+    ptb: &Ptb,
+    gas_coin: ObjectID,
+) -> core::result::Result<Changes, String> {
+    let mut changes = Changes::default();
+
+    for (index, command) in ptb.commands.iter().enumerate() {
+        match command {
+            PtbCommand::Call(call) => {
+                apply_call(state, package_id, signer, call, index, &mut changes)?
+            }
+            PtbCommand::TransferSui { recipient, amount } => {
+                transfer_sui(state, gas_coin, *recipient, *amount, index)?
+            }
+        }
+    }
+
+    // -- An object created by the block is only reported as created.
+    changes.mutated.retain(|id| !changes.created.contains(id));
+
+    Ok(changes)
+}
+
+fn apply_call(
+    state: &mut FakeState,
+    package_id: &ObjectID,
+    signer: SuiAddress,
+    call: &MoveCall,
+    command: usize,
+    changes: &mut Changes,
+) -> core::result::Result<(), String> {
+    let abort = |code| move_abort(package_id, call.function, code, command);
+    let object_arg = |index| object_arg(call, index, command);
+    let address_arg = |index| address_arg(call, index, command);
+    let u64_arg = |index| u64_arg(call, index, command);
+
+    match call.function {
+        "create_profile" => {
+            let game_id = object_arg(0)?;
+            let admin = object_arg(1)?;
+            let player = address_arg(2)?;
+            owned(state, admin, signer, ADMIN_OBJECT_NAME).map_err(abort)?;
+
+            let hero = state.new_id();
+            let hero_fields = MoveFields::from([
+                uid_field(hero),
+                number_field("level", HERO_LEVEL),
+                address_field("game_id", game_id),
+            ]);
+            state.insert(hero, player, HERO_OBJECT_NAME, hero_fields);
+
+            let pet = state.new_id();
+            let pet_fields = MoveFields::from([
+                uid_field(pet),
+                number_field("hp", PET_HP),
+                number_field("exp", 0),
+                address_field("game_id", game_id),
+                number_field("strength", PET_STRENGTH),
+            ]);
+            state.insert(pet, player, PET_OBJECT_NAME, pet_fields);
+
+            changes.created.extend([hero, pet]);
+        }
+        "send_bot" => {
+            let game_id = object_arg(0)?;
+            let admin = object_arg(1)?;
+            let player = address_arg(2)?;
+            let hp = u64_arg(3)?;
+            let strength = u64_arg(4)?;
+            owned(state, admin, signer, ADMIN_OBJECT_NAME).map_err(abort)?;
+
+            let bot = state.new_id();
+            let bot_fields = MoveFields::from([
+                uid_field(bot),
+                number_field("hp", hp),
+                address_field("game_id", game_id),
+                number_field("strength", strength),
+            ]);
+            state.insert(bot, player, BOT_OBJECT_NAME, bot_fields);
+            add_to_field(state, admin, "bot_animal_created", 1);
+
+            changes.created.push(bot);
+            changes.mutated.push(admin);
+        }
+        "huntbot" => {
+            let hero = object_arg(1)?;
+            let pet = object_arg(2)?;
+            let bot = object_arg(3)?;
+            owned(state, hero, signer, HERO_OBJECT_NAME).map_err(abort)?;
+            owned(state, pet, signer, PET_OBJECT_NAME).map_err(abort)?;
+            owned(state, bot, signer, BOT_OBJECT_NAME).map_err(abort)?;
+
+            let bot_hp = state
+                .objects
+                .remove(&bot)
+                .map(|obj| field_number(&obj.fields, "hp"))
+                .unwrap_or_default();
+            add_to_field(state, pet, "exp", bot_hp);
+
+            changes.mutated.push(pet);
+            changes.deleted.push((bot, BOT_OBJECT_NAME));
+        }
+        "hunt" => {
+            let hero = object_arg(1)?;
+            let pet = object_arg(2)?;
+            let coin = object_arg(3)?;
+            let stake = u64_arg(4)?;
+            owned(state, hero, signer, HERO_OBJECT_NAME).map_err(abort)?;
+            owned(state, pet, signer, PET_OBJECT_NAME).map_err(abort)?;
+
+            match state.coins.get_mut(&coin) {
+                Some((owner, balance)) if *owner == signer && *balance >= stake => {
+                    *balance -= stake;
+                }
+                Some((owner, _)) if *owner == signer => return Err(abort(ENOT_ENOUGH_COIN)),
+                _ => return Err(abort(ENOT_OWNER)),
+            }
+            add_to_field(state, pet, "exp", stake / HUNT_EXP_UNIT);
+
+            changes.mutated.push(pet);
+        }
+        function => {
+            return Err(format!(
+                "FunctionNotFound({MODULE_NAME}::{function}) in command {command}"
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+/// Split `amount` from the gas coin as a new coin for `recipient`.
+fn transfer_sui(
+    state: &mut FakeState,
+    gas_coin: ObjectID,
+    recipient: SuiAddress,
+    amount: u64,
+    command: usize,
+) -> core::result::Result<(), String> {
+    let balance = state
+        .coins
+        .get_mut(&gas_coin)
+        .map(|(_, balance)| balance)
+        .filter(|balance| **balance >= amount)
+        .ok_or(format!("InsufficientCoinBalance in command {command}"))?;
+    *balance -= amount;
+
+    let coin = state.new_id();
+    state.coins.insert(coin, (recipient, amount));
+
+    Ok(())
+}
+
+/// The richest coin of `signer` that is not an object input of `ptb`, a coin
+/// can not pay the gas and be an argument of the same transaction.
+fn select_gas(state: &FakeState, signer: SuiAddress, ptb: &Ptb) -> Option<ObjectID> {
+    let inputs: Vec<ObjectID> = ptb
+        .commands
+        .iter()
+        .filter_map(|command| match command {
+            PtbCommand::Call(call) => Some(&call.args),
+            PtbCommand::TransferSui { .. } => None,
+        })
+        .flatten()
+        .filter_map(|arg| match arg {
+            MoveArg::Object(id) => ObjectID::from_str(id).ok(),
+            _ => None,
+        })
+        .collect();
+
+    state
+        .coins
+        .iter()
+        .filter(|(id, (owner, _))| *owner == signer && !inputs.contains(id))
+        .max_by_key(|(_, (_, balance))| *balance)
+        .map(|(id, _)| *id)
+}
+
+/// Abort code of a missing, foreign or mistyped object.
+fn owned(
+    state: &FakeState,
+    id: ObjectID,
+    signer: SuiAddress,
+    name: &'static str,
+) -> core::result::Result<(), u64> {
+    match state.objects.get(&id) {
+        Some(obj) if obj.owner == signer && obj.name == name => Ok(()),
+        _ => Err(ENOT_OWNER),
+    }
+}
+
+/// Same format as the fullnode, see `MoveAbort::parse`.
+fn move_abort(package_id: &ObjectID, function: &str, code: u64, command: usize) -> String {
+    format!(
+        "MoveAbort(MoveLocation {{ module: ModuleId {{ address: {package_id}, name: Identifier(\"{MODULE_NAME}\") }}, function: 0, instruction: 0, function_name: Some(\"{function}\") }}, {code}) in command {command}"
+    )
+}
+
+fn gas_cost(changes: &Changes) -> GasCostSummary {
+    let storage_cost = FAKE_STORAGE_COST * changes.created.len() as u64;
+
+    GasCostSummary::new(FAKE_COMPUTATION_COST, storage_cost, 0, 0)
+}
+
+/// An empty transaction, only its sender, gas coin and budget mean something.
+fn placeholder_tx(signer: SuiAddress, gas_budget: u64, gas_coin: ObjectID) -> TransactionData {
+    let gas_coin = (gas_coin, SequenceNumber::new(), ObjectDigest::new([0; 32]));
+
+    TransactionData::new_programmable(
+        signer,
+        vec![gas_coin],
+        ProgrammableTransaction {
+            inputs: vec![],
+            commands: vec![],
+        },
+        gas_budget,
+        FAKE_GAS_PRICE,
+    )
+}
+// endregion: --- Move Functions
+
+// region:    --- Arguments & Fields
+
+fn bad_argument(index: usize, command: usize) -> String {
+    format!("CommandArgumentError {{ arg_idx: {index}, kind: TypeMismatch }} in command {command}")
+}
+
+fn object_arg(
+    call: &MoveCall,
+    index: usize,
+    command: usize,
+) -> core::result::Result<ObjectID, String> {
+    match call.args.get(index) {
+        Some(MoveArg::Object(id)) => {
+            ObjectID::from_str(id).map_err(|_| bad_argument(index, command))
+        }
+        _ => Err(bad_argument(index, command)),
+    }
+}
+
+fn address_arg(
+    call: &MoveCall,
+    index: usize,
+    command: usize,
+) -> core::result::Result<SuiAddress, String> {
+    match call.args.get(index) {
+        Some(MoveArg::Address(address)) => Ok(*address),
+        _ => Err(bad_argument(index, command)),
+    }
+}
+
+fn u64_arg(call: &MoveCall, index: usize, command: usize) -> core::result::Result<u64, String> {
+    match call.args.get(index) {
+        Some(MoveArg::U8(value)) => Ok(u64::from(*value)),
+        Some(MoveArg::U64(value)) => Ok(*value),
+        _ => Err(bad_argument(index, command)),
+    }
+}
+
+fn uid_field(id: ObjectID) -> (String, SuiMoveValue) {
+    ("id".to_string(), SuiMoveValue::UID { id })
+}
+
+fn address_field(name: &str, id: ObjectID) -> (String, SuiMoveValue) {
+    (
+        name.to_string(),
+        SuiMoveValue::Address(SuiAddress::from(id)),
+    )
+}
+
+/// Move `u64` fields come back as decimal strings from the fullnode.
+fn number_field(name: &str, value: u64) -> (String, SuiMoveValue) {
+    (name.to_string(), SuiMoveValue::String(value.to_string()))
+}
+
+fn field_number(fields: &MoveFields, name: &str) -> u64 {
+    match fields.get(name) {
+        Some(SuiMoveValue::String(value)) => value.parse().unwrap_or_default(),
+        Some(SuiMoveValue::Number(value)) => u64::from(*value),
+        _ => 0,
+    }
+}
+
+fn add_to_field(state: &mut FakeState, id: ObjectID, name: &str, delta: u64) {
+    if let Some(obj) = state.objects.get_mut(&id) {
+        let value = field_number(&obj.fields, name).saturating_add(delta);
+        obj.fields.extend([number_field(name, value)]);
+    }
+}
+// endregion: --- Arguments & Fields
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use dotenvy::dotenv;
+    use sui_types::base_types::SuiAddress;
+
+    use super::{SuiGateway, FAKE_FAUCET_AMOUNT};
+    use crate::_dev_init;
+    use crate::get_config;
+    use crate::sui_call::call_api::{Error, MoveCall};
+    use crate::sui_call::{BOT_OBJECT_NAME, HERO_OBJECT_NAME, PET_OBJECT_NAME};
+
+    fn player() -> SuiAddress {
+        SuiAddress::from_str("0xdb96399b7daeac4613a8494a30cf371206cff2ea4d19924d87ddf151d0d3a1c7")
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_faucet_success() {
+        dotenv().ok();
+
+        let (sui, _executor) = _dev_init::init_sui_for_test();
+
+        sui.request_faucet(player()).await.unwrap();
+
+        let coins = sui.get_coins(player()).await.unwrap();
+        assert_eq!(coins.len(), 1);
+        assert_eq!(coins[0].balance, FAKE_FAUCET_AMOUNT);
+    }
+
+    #[tokio::test]
+    async fn test_huntbot_deletes_bot() {
+        dotenv().ok();
+
+        let config = get_config();
+        let (sui, executor) = _dev_init::init_sui_for_test();
+        let admin = SuiAddress::from_str(&config.SUI_CLIENT_ADDRESS).unwrap();
+
+        let profile = MoveCall::new("create_profile")
+            .object(&config.GAME_INFO_ID)
+            .object(&config.GAME_ADMIN_ID)
+            .address(player());
+        let effects = executor.execute(admin, profile).await.unwrap();
+        let hero = effects
+            .created_object(&executor.game_type(HERO_OBJECT_NAME))
+            .unwrap()
+            .object_id;
+        let pet = effects
+            .created_object(&executor.game_type(PET_OBJECT_NAME))
+            .unwrap()
+            .object_id;
+
+        let send_bot = MoveCall::new("send_bot")
+            .object(&config.GAME_INFO_ID)
+            .object(&config.GAME_ADMIN_ID)
+            .address(player())
+            .u8(40)
+            .u8(4);
+        let effects = executor.execute(admin, send_bot).await.unwrap();
+        let bot = effects
+            .created_object(&executor.game_type(BOT_OBJECT_NAME))
+            .unwrap()
+            .object_id;
+
+        sui.request_faucet(player()).await.unwrap();
+        let huntbot = MoveCall::new("huntbot")
+            .object(&config.GAME_INFO_ID)
+            .object(hero)
+            .object(pet)
+            .object(bot);
+        let effects = executor.execute(player(), huntbot).await.unwrap();
+
+        let deleted = effects.deleted_object(&executor.game_type(BOT_OBJECT_NAME));
+        assert_eq!(deleted.unwrap().object_id, bot);
+        let mutated = effects.mutated_object(&executor.game_type(PET_OBJECT_NAME));
+        assert_eq!(mutated.unwrap().object_id, pet);
+        assert!(sui.object_fields(&bot).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_hunt_not_enough_coin_aborts() {
+        dotenv().ok();
+
+        let config = get_config();
+        let (sui, executor) = _dev_init::init_sui_for_test();
+        let admin = SuiAddress::from_str(&config.SUI_CLIENT_ADDRESS).unwrap();
+
+        let profile = MoveCall::new("create_profile")
+            .object(&config.GAME_INFO_ID)
+            .object(&config.GAME_ADMIN_ID)
+            .address(player());
+        let effects = executor.execute(admin, profile).await.unwrap();
+        let hero = effects
+            .created_object(&executor.game_type(HERO_OBJECT_NAME))
+            .unwrap()
+            .object_id;
+        let pet = effects
+            .created_object(&executor.game_type(PET_OBJECT_NAME))
+            .unwrap()
+            .object_id;
+        let coin = sui.add_coin(player(), 100);
+        sui.request_faucet(player()).await.unwrap();
+
+        let hunt = MoveCall::new("hunt")
+            .object(&config.GAME_INFO_ID)
+            .object(hero)
+            .object(pet)
+            .object(coin)
+            .u64(1_000);
+        let res = executor.execute(player(), hunt).await;
+
+        // -- Caught by the dry run, the coin is untouched.
+        assert!(matches!(
+            res,
+            Err(Error::DryRunFail { function: "hunt", reason })
+                if reason == "we_pet_game::hunt aborted with code 2"
+        ));
+        assert_eq!(sui.coin_balance(&coin), Some(100));
+    }
+
+    #[tokio::test]
+    async fn test_hunt_stake_coin_never_pays_gas() {
+        dotenv().ok();
+
+        let config = get_config();
+        let (sui, executor) = _dev_init::init_sui_for_test();
+        let admin = SuiAddress::from_str(&config.SUI_CLIENT_ADDRESS).unwrap();
+
+        let profile = MoveCall::new("create_profile")
+            .object(&config.GAME_INFO_ID)
+            .object(&config.GAME_ADMIN_ID)
+            .address(player());
+        let effects = executor.execute(admin, profile).await.unwrap();
+        let hero = effects
+            .created_object(&executor.game_type(HERO_OBJECT_NAME))
+            .unwrap()
+            .object_id;
+        let pet = effects
+            .created_object(&executor.game_type(PET_OBJECT_NAME))
+            .unwrap()
+            .object_id;
+        let hunt = |coin| {
+            MoveCall::new("hunt")
+                .object(&config.GAME_INFO_ID)
+                .object(hero)
+                .object(pet)
+                .object(coin)
+                .u64(1_000)
+        };
+
+        // -- The only coin of the player is the stake, no gas coin is left.
+        let coin = sui.add_coin(player(), FAKE_FAUCET_AMOUNT);
+        let res = executor.execute(player(), hunt(coin)).await;
+
+        assert!(matches!(res, Err(Error::BuildTransaction(_))));
+        assert_eq!(sui.coin_balance(&coin), Some(FAKE_FAUCET_AMOUNT));
+
+        // -- A second, smaller coin pays the gas.
+        sui.add_coin(player(), 10);
+        executor.execute(player(), hunt(coin)).await.unwrap();
+
+        assert_eq!(sui.coin_balance(&coin), Some(FAKE_FAUCET_AMOUNT - 1_000));
+    }
+}
+// endregion:    --- Tests
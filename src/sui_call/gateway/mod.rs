@@ -0,0 +1,83 @@
+//! The calls the game makes on a sui network, behind a trait so the
+//! commands and the worker can run against the in-memory `FakeGateway`.
+
+mod fake;
+mod rpc;
+
+pub use self::fake::{FakeGateway, FAKE_FAUCET_AMOUNT};
+pub use self::rpc::RpcGateway;
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use move_core_types::language_storage::StructTag;
+use sui_json_rpc_types::{Coin, SuiExecutionStatus, SuiMoveValue};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::gas::GasCostSummary;
+use sui_types::transaction::TransactionData;
+
+use super::call_api::{self, CallEffects, GameKeystore, Ptb};
+use super::Result;
+
+/// The fields of a move object, by field name.
+pub type MoveFields = BTreeMap<String, SuiMoveValue>;
+
+/// One page of owned objects, `None` for an object without move content.
+pub struct FieldsPage {
+    pub data: Vec<Option<MoveFields>>,
+    pub next_cursor: Option<ObjectID>,
+    pub has_next_page: bool,
+}
+
+/// A transaction built and dry-run, ready to sign.
+pub struct PreparedCall {
+    pub signer: SuiAddress,
+    pub ptb: Ptb,
+    /// The coin paying the gas, `None` when left to the gateway.
+    pub gas_coin: Option<ObjectID>,
+    pub tx_data: TransactionData,
+    pub dry_run_status: SuiExecutionStatus,
+    pub dry_run_cost: GasCostSummary,
+}
+
+#[async_trait]
+pub trait SuiGateway: Send + Sync {
+    // -- Read
+
+    /// One page of the objects of type `object_type` owned by `owner`.
+    async fn get_owned_objects(
+        &self,
+        owner: SuiAddress,
+        object_type: StructTag,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<FieldsPage>;
+
+    /// The SUI coins of `owner`.
+    async fn get_coins(&self, owner: SuiAddress) -> Result<Vec<Coin>>;
+
+    /// Send test SUI to `address`, on networks with a faucet.
+    async fn request_faucet(&self, address: SuiAddress) -> Result<()>;
+
+    // -- Call
+
+    /// Build the transaction of `ptb` with a `gas_budget` budget and dry-run it.
+    ///
+    /// `gas_coin` pays the gas, a coin of `signer` is picked when `None`, never
+    /// one of the object inputs of `ptb`.
+    async fn prepare_ptb(
+        &self,
+        signer: SuiAddress,
+        package_id: ObjectID,
+        ptb: &Ptb,
+        gas_budget: u64,
+        gas_coin: Option<ObjectID>,
+    ) -> call_api::Result<PreparedCall>;
+
+    /// Sign the prepared transaction with `keystore` and execute it.
+    async fn sign_and_execute(
+        &self,
+        prepared: PreparedCall,
+        keystore: &GameKeystore,
+    ) -> call_api::Result<CallEffects>;
+}
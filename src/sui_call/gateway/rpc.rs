@@ -0,0 +1,214 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use move_core_types::language_storage::StructTag;
+use shared_crypto::intent::Intent;
+use sui_json_rpc_types::{
+    Coin, SuiMoveStruct, SuiObjectData, SuiObjectDataFilter, SuiObjectDataOptions,
+    SuiObjectResponse, SuiObjectResponseQuery, SuiParsedData, SuiParsedMoveObject,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions,
+};
+use sui_sdk::SuiClient;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::transaction::{CallArg, Command, ObjectArg, Transaction, TransactionKind};
+use sui_types::Identifier;
+
+use super::{FieldsPage, MoveFields, PreparedCall, SuiGateway};
+use crate::sui_call::call_api::{self, CallEffects, Error, GameKeystore, Ptb, PtbCommand};
+use crate::sui_call::network::SuiNetwork;
+use crate::sui_call::utils::request_tokens_from_faucet;
+use crate::sui_call::Result;
+
+/// The gateway of a real network, through its json rpc fullnode.
+#[derive(Clone)]
+pub struct RpcGateway {
+    client: SuiClient,
+    network: SuiNetwork,
+}
+
+impl RpcGateway {
+    pub fn new(client: SuiClient, network: SuiNetwork) -> Self {
+        RpcGateway { client, network }
+    }
+}
+
+#[async_trait]
+impl SuiGateway for RpcGateway {
+    async fn get_owned_objects(
+        &self,
+        owner: SuiAddress,
+        object_type: StructTag,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<FieldsPage> {
+        let query = SuiObjectResponseQuery::new(
+            Some(SuiObjectDataFilter::StructType(object_type)),
+            Some(SuiObjectDataOptions::new().with_content()),
+        );
+
+        let page = self
+            .client
+            .read_api()
+            .get_owned_objects(owner, Some(query), cursor, limit)
+            .await?;
+
+        Ok(FieldsPage {
+            data: page.data.into_iter().map(move_fields).collect(),
+            next_cursor: page.next_cursor,
+            has_next_page: page.has_next_page,
+        })
+    }
+
+    async fn get_coins(&self, owner: SuiAddress) -> Result<Vec<Coin>> {
+        Ok(self
+            .client
+            .coin_read_api()
+            .get_coins_stream(owner, None)
+            .collect()
+            .await)
+    }
+
+    async fn request_faucet(&self, address: SuiAddress) -> Result<()> {
+        request_tokens_from_faucet(address, &self.network).await
+    }
+
+    async fn prepare_ptb(
+        &self,
+        signer: SuiAddress,
+        package_id: ObjectID,
+        ptb: &Ptb,
+        gas_budget: u64,
+        gas_coin: Option<ObjectID>,
+    ) -> call_api::Result<PreparedCall> {
+        let build_error = |e: anyhow::Error| Error::BuildTransaction(e.to_string());
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        for command in &ptb.commands {
+            match command {
+                PtbCommand::Call(call) => {
+                    let module = Identifier::from_str(call.module).map_err(build_error)?;
+                    let function = Identifier::from_str(call.function).map_err(build_error)?;
+
+                    let arguments = self
+                        .client
+                        .transaction_builder()
+                        .resolve_and_checks_json_args(
+                            &mut builder,
+                            package_id,
+                            &module,
+                            &function,
+                            &[],
+                            call.sui_json_args()?,
+                        )
+                        .await
+                        .map_err(build_error)?;
+
+                    builder.command(Command::move_call(
+                        package_id,
+                        module,
+                        function,
+                        vec![],
+                        arguments,
+                    ));
+                }
+                PtbCommand::TransferSui { recipient, amount } => builder
+                    .pay_sui(vec![*recipient], vec![*amount])
+                    .map_err(build_error)?,
+            }
+        }
+
+        let gas_price = self
+            .client
+            .read_api()
+            .get_reference_gas_price()
+            .await
+            .map_err(|e| Error::BuildTransaction(e.to_string()))?;
+
+        // -- An owned input (e.g. the `/hunt` stake coin) must not be picked as gas.
+        let pt = builder.finish();
+        let input_objects: Vec<ObjectID> = pt
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _))) => Some(*id),
+                _ => None,
+            })
+            .collect();
+
+        let tx_data = self
+            .client
+            .transaction_builder()
+            .tx_data(
+                signer,
+                TransactionKind::programmable(pt),
+                gas_budget,
+                gas_price,
+                input_objects,
+                gas_coin,
+            )
+            .await
+            .map_err(build_error)?;
+
+        let dry_run = self
+            .client
+            .read_api()
+            .dry_run_transaction_block(tx_data.clone())
+            .await
+            .map_err(|e| Error::DryRun(e.to_string()))?;
+
+        Ok(PreparedCall {
+            signer,
+            ptb: ptb.clone(),
+            gas_coin,
+            tx_data,
+            dry_run_status: dry_run.effects.status().clone(),
+            dry_run_cost: dry_run.effects.gas_cost_summary().clone(),
+        })
+    }
+
+    async fn sign_and_execute(
+        &self,
+        prepared: PreparedCall,
+        keystore: &GameKeystore,
+    ) -> call_api::Result<CallEffects> {
+        let signature = keystore.sign(&prepared.signer, &prepared.tx_data).await?;
+
+        let response = self
+            .client
+            .quorum_driver_api()
+            .execute_transaction_block(
+                Transaction::from_data(
+                    prepared.tx_data,
+                    Intent::sui_transaction(),
+                    vec![signature],
+                ),
+                SuiTransactionBlockResponseOptions::full_content(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await
+            .map_err(|e| Error::Execution(e.to_string()))?;
+
+        CallEffects::try_from(response)
+    }
+}
+
+fn move_fields(response: SuiObjectResponse) -> Option<MoveFields> {
+    match response {
+        SuiObjectResponse {
+            data:
+                Some(SuiObjectData {
+                    content:
+                        Some(SuiParsedData::MoveObject(SuiParsedMoveObject {
+                            fields: SuiMoveStruct::WithFields(field_map),
+                            ..
+                        })),
+                    ..
+                }),
+            ..
+        } => Some(field_map),
+        _ => None,
+    }
+}
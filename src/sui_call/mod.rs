@@ -1,4 +1,10 @@
+use move_core_types::language_storage::StructTag;
+use sui_types::base_types::ObjectID;
+use sui_types::Identifier;
+
 pub mod call_api;
+pub mod gateway;
+pub mod network;
 pub mod read_api;
 pub mod sui_move_object;
 pub mod utils;
@@ -10,3 +16,13 @@ pub const BOT_OBJECT_NAME: &str = "Bot";
 pub const PET_OBJECT_NAME: &str = "Pet";
 pub const HERO_OBJECT_NAME: &str = "Hero";
 pub const MODULE_NAME: &str = "we_pet_game";
+
+/// The move type of the game struct `name` published at `package_id`.
+pub fn game_type(package_id: ObjectID, name: &str) -> StructTag {
+    StructTag {
+        address: *package_id,
+        module: Identifier::new(MODULE_NAME).expect("valid module name"),
+        name: Identifier::new(name).expect("valid struct name"),
+        type_params: vec![],
+    }
+}
@@ -0,0 +1,71 @@
+use sui_sdk::error::SuiRpcResult;
+use sui_sdk::{SuiClient, SuiClientBuilder};
+
+/// The sui network the server plays on, see `SUI_NETWORK` in the config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuiNetwork {
+    pub name: String,
+    pub rpc_url: String,
+    pub ws_url: Option<String>,
+    pub faucet_url: Option<String>,
+}
+
+impl SuiNetwork {
+    /// Public endpoints of the known networks (`localnet` is `sui start`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        let (rpc_url, ws_url, faucet_url) = match name {
+            "localnet" => (
+                "http://127.0.0.1:9000",
+                Some("ws://127.0.0.1:9000"),
+                Some("http://127.0.0.1:9123"),
+            ),
+            "devnet" => (
+                "https://fullnode.devnet.sui.io:443",
+                None,
+                Some("https://faucet.devnet.sui.io"),
+            ),
+            "testnet" => (
+                "https://fullnode.testnet.sui.io:443",
+                None,
+                Some("https://faucet.testnet.sui.io/v1"),
+            ),
+            "mainnet" => ("https://fullnode.mainnet.sui.io:443", None, None),
+            _ => return None,
+        };
+
+        Some(SuiNetwork {
+            name: name.to_string(),
+            rpc_url: rpc_url.to_string(),
+            ws_url: ws_url.map(str::to_string),
+            faucet_url: faucet_url.map(str::to_string),
+        })
+    }
+
+    pub async fn build_client(&self) -> SuiRpcResult<SuiClient> {
+        let mut builder = SuiClientBuilder::default();
+        if let Some(ws_url) = &self.ws_url {
+            builder = builder.ws_url(ws_url);
+        }
+
+        builder.build(&self.rpc_url).await
+    }
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::SuiNetwork;
+
+    #[test]
+    fn test_network_from_name() {
+        let localnet = SuiNetwork::from_name("localnet").unwrap();
+        assert_eq!(localnet.rpc_url, "http://127.0.0.1:9000");
+        assert!(localnet.faucet_url.is_some());
+
+        let mainnet = SuiNetwork::from_name("mainnet").unwrap();
+        assert_eq!(mainnet.faucet_url, None);
+
+        assert_eq!(SuiNetwork::from_name("moonnet"), None);
+    }
+}
+// endregion:    --- Tests
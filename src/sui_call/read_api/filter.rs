@@ -1,15 +1,8 @@
 use move_core_types::{account_address::AccountAddress, language_storage::StructTag};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
-use sui_json_rpc_types::SuiObjectDataFilter;
 use sui_types::{base_types::ObjectID, Identifier, TypeTag};
 
-pub fn new_filter(sui_struct_tag: SuiStructTag) -> SuiObjectDataFilter {
-    SuiObjectDataFilter::StructType(StructTag {
-        ..sui_struct_tag.into()
-    })
-}
-
 // region: --- Struct Tag (Builder)
 #[derive(Serialize, Deserialize, Debug, PartialEq, Hash, Eq, Clone, PartialOrd, Ord)]
 pub struct SuiStructTag {
@@ -1,31 +1,25 @@
-use super::filter::{new_filter, SuiStructTag};
-use crate::{
-    config::Config,
-    get_config,
-    sui_call::{
-        sui_move_object::{
-            admin_obj::SuiAdminObject, bot_obj::SuiBotObject, pet_obj::SuiPetObject,
-            FromSuiMoveStruct,
-        },
-        Result, ADMIN_OBJECT_NAME, BOT_OBJECT_NAME, MODULE_NAME,
-    },
-};
-use sqlx::any;
-use sui_json_rpc_types::{
-    SuiMoveStruct, SuiObjectData, SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse,
-    SuiObjectResponseQuery, SuiParsedData, SuiParsedMoveObject,
+use super::filter::SuiStructTag;
+use crate::sui_call::{
+    gateway::{FieldsPage, MoveFields, SuiGateway},
+    sui_move_object::{self, FromSuiMoveStruct},
+    Result, MODULE_NAME,
 };
-use sui_sdk::SuiClient;
+use futures::stream::{self, Stream, StreamExt};
+use move_core_types::language_storage::StructTag;
+use std::pin::pin;
+use std::sync::Arc;
+use sui_sdk::error::Error as SuiRpcError;
 use sui_types::base_types::{ObjectID, SuiAddress};
+use tracing::debug;
 
 pub struct WePetGame {
-    sui: SuiClient,
+    sui: Arc<dyn SuiGateway>,
     adrr: SuiAddress,
     package_id: String,
 }
 
 impl WePetGame {
-    pub fn new(sui: SuiClient, adrr: SuiAddress, package_id: &str) -> Self {
+    pub fn new(sui: Arc<dyn SuiGateway>, adrr: SuiAddress, package_id: &str) -> Self {
         WePetGame {
             sui,
             adrr,
@@ -34,93 +28,126 @@ impl WePetGame {
     }
 }
 
+// Max page size of `get_owned_objects`.
+const OWNED_OBJECTS_PAGE_SIZE: usize = 50;
+
+/// One page of owned objects, `next_cursor` continues the listing.
+pub struct ObjectPage<T> {
+    pub items: Vec<Result<T>>,
+    pub next_cursor: Option<ObjectID>,
+    pub has_next_page: bool,
+}
+
 impl WePetGame {
     pub async fn get_sui_obj_first<T>(&self, name: &str) -> Result<T>
     where
         T: FromSuiMoveStruct,
     {
-        let sui_data_filter = new_filter(
-            SuiStructTag::builder()
-                .package(self.package_id.as_str())
-                .module(MODULE_NAME)
-                .name(name)
-                .build(),
-        );
+        let page = self.get_fields_page(name, None, Some(1)).await?;
 
-        let query = new_default_query(sui_data_filter);
-
-        let response = get_objects_response(&self.sui, &self.adrr, query)
-            .await?
-            .into_iter()
-            .next();
-
-        process_response::<T>(response).await
+        process_response::<T>(page.data.into_iter().next().flatten())
     }
 
+    /// All the owned objects of the game struct `name`.
+    ///
+    /// Objects without move content are skipped, use `get_sui_objs_stream`
+    /// to get the error of each object. A field that does not match the
+    /// move struct fails the whole listing.
     pub async fn get_sui_objs<T>(&self, name: &str) -> Result<Vec<T>>
     where
         T: FromSuiMoveStruct,
     {
-        let sui_data_filter = new_filter(
-            SuiStructTag::builder()
-                .package(self.package_id.as_str())
-                .module(MODULE_NAME)
-                .name(name)
-                .build(),
-        );
+        let mut objs = Vec::new();
+
+        let mut stream = pin!(self.get_sui_objs_stream::<T>(name));
+        while let Some(obj) = stream.next().await {
+            match obj {
+                Ok(obj) => objs.push(obj),
+                Err(e) if e.is::<SuiRpcError>() || e.is::<sui_move_object::Error>() => {
+                    return Err(e)
+                }
+                Err(e) => debug!("{:<12} - skip {name} object: {e:?}", "READ_API"),
+            }
+        }
+
+        Ok(objs)
+    }
 
-        let query = new_default_query(sui_data_filter.clone());
+    /// Stream the owned objects of the game struct `name`, following the
+    /// cursor to the last page. A failed page request ends the stream.
+    pub fn get_sui_objs_stream<'a, T>(&'a self, name: &'a str) -> impl Stream<Item = Result<T>> + 'a
+    where
+        T: FromSuiMoveStruct + 'a,
+    {
+        // -- (cursor, done)
+        stream::unfold((None, false), move |(cursor, done)| async move {
+            if done {
+                return None;
+            }
 
-        let response = get_objects_response(&self.sui, &self.adrr, query).await?;
+            match self
+                .get_sui_objs_page::<T>(name, cursor, OWNED_OBJECTS_PAGE_SIZE)
+                .await
+            {
+                Ok(page) => {
+                    let done = !page.has_next_page || page.next_cursor.is_none();
+                    Some((page.items, (page.next_cursor, done)))
+                }
+                Err(e) => Some((vec![Err(e)], (None, true))),
+            }
+        })
+        .flat_map(stream::iter)
+    }
 
-        let mut vec = Vec::new();
+    /// One page of the owned objects of the game struct `name`, for list views.
+    pub async fn get_sui_objs_page<T>(
+        &self,
+        name: &str,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<ObjectPage<T>>
+    where
+        T: FromSuiMoveStruct,
+    {
+        let page = self.get_fields_page(name, cursor, Some(limit)).await?;
 
-        for i in response {
-            vec.push(process_response::<T>(Some(i)).await?)
-        }
+        let items = page.data.into_iter().map(process_response::<T>).collect();
 
-        Ok(vec)
+        Ok(ObjectPage {
+            items,
+            next_cursor: page.next_cursor,
+            has_next_page: page.has_next_page,
+        })
     }
-}
 
-fn new_default_query(sui_data_filter: SuiObjectDataFilter) -> SuiObjectResponseQuery {
-    SuiObjectResponseQuery::new(
-        Some(sui_data_filter),
-        Some(SuiObjectDataOptions::new().with_content()),
-    )
-}
+    async fn get_fields_page(
+        &self,
+        name: &str,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<FieldsPage> {
+        self.sui
+            .get_owned_objects(self.adrr, self.object_type(name), cursor, limit)
+            .await
+    }
 
-async fn get_objects_response(
-    sui: &SuiClient,
-    adrr: &SuiAddress,
-    query: SuiObjectResponseQuery,
-) -> Result<Vec<SuiObjectResponse>> {
-    Ok(sui
-        .read_api()
-        .get_owned_objects(*adrr, Some(query), None, Some(10))
-        .await?
-        .data)
+    fn object_type(&self, name: &str) -> StructTag {
+        SuiStructTag::builder()
+            .package(self.package_id.as_str())
+            .module(MODULE_NAME)
+            .name(name)
+            .build()
+            .into()
+    }
 }
 
-async fn process_response<T>(response: Option<SuiObjectResponse>) -> Result<T>
+fn process_response<T>(fields: Option<MoveFields>) -> Result<T>
 where
     T: FromSuiMoveStruct,
 {
-    if let Some(SuiObjectResponse {
-        data:
-            Some(SuiObjectData {
-                content:
-                    Some(SuiParsedData::MoveObject(SuiParsedMoveObject {
-                        fields: SuiMoveStruct::WithFields(field_map),
-                        ..
-                    })),
-                ..
-            }),
-        ..
-    }) = response
-    {
-        println!("{:?}", field_map);
-        let obj: T = FromSuiMoveStruct::from_sui_move_struct(field_map);
+    if let Some(field_map) = fields {
+        debug!("{:?}", field_map);
+        let obj: T = FromSuiMoveStruct::from_sui_move_struct(field_map)?;
         Ok(obj)
     } else {
         Err(anyhow::Error::msg("No valid response"))
@@ -130,18 +157,16 @@ where
 // region:    --- Tests
 #[cfg(test)]
 mod tests {
-    use std::{path::Path, str::FromStr};
+    use std::str::FromStr;
 
     use dotenvy::dotenv;
-    use serial_test::serial;
-    use sui_keys::keystore::{FileBasedKeystore, Keystore};
-    use sui_sdk::{SuiClient, SuiClientBuilder};
+    use futures::StreamExt;
     use sui_types::base_types::{ObjectID, SuiAddress};
-    use tracing::{debug, info};
 
     use crate::{
-        get_config,
+        _dev_init, get_config,
         sui_call::{
+            call_api::create_profile::create_profile,
             sui_move_object::{
                 admin_obj::SuiAdminObject, bot_obj::SuiBotObject, hero_obj::SuiHeroObject,
                 pet_obj::SuiPetObject,
@@ -152,56 +177,86 @@ mod tests {
 
     use super::WePetGame;
 
-    #[serial]
+    fn player() -> SuiAddress {
+        SuiAddress::from_str("0x64f804ad5f8bf531d507a2dd4e00c7de041c8c6ced7744bbe66d93fedf8dfb7f")
+            .unwrap()
+    }
+
     #[tokio::test]
     async fn test_get_bot_obj_success() {
         dotenv().ok();
 
         let config = get_config();
+        let (sui, executor) = _dev_init::init_sui_for_test();
+        let package_id = executor.package_id().to_string();
+        let admin = SuiAddress::from_str(&config.SUI_CLIENT_ADDRESS).unwrap();
 
-        let keystore_path = Path::new("/home/ganzzi/.sui/sui_config/sui.keystore");
-        let mut keystore =
-            Keystore::from(FileBasedKeystore::new(&keystore_path.to_path_buf()).unwrap());
-
-        let sui_client = SuiClientBuilder::default().build_devnet().await.unwrap();
-        let package_id = ObjectID::from_str(&config.PACKAGE).unwrap();
-        let player = SuiAddress::from_str(
-            "0x64f804ad5f8bf531d507a2dd4e00c7de041c8c6ced7744bbe66d93fedf8dfb7f",
-        )
-        .unwrap();
-        let admin = SuiAddress::from_str(
-            "0xdb96399b7daeac4613a8494a30cf371206cff2ea4d19924d87ddf151d0d3a1c7",
-        )
-        .unwrap();
+        create_profile(&executor, player()).await.unwrap();
 
-        let obj = WePetGame::new(sui_client.clone(), player, package_id.to_string().as_str());
-        let obj_admin = WePetGame::new(sui_client, admin, package_id.to_string().as_str());
+        let obj = WePetGame::new(sui.clone(), player(), &package_id);
+        let obj_admin = WePetGame::new(sui, admin, &package_id);
 
         let admin = obj_admin
             .get_sui_obj_first::<SuiAdminObject>(ADMIN_OBJECT_NAME)
             .await
-            .map_err(|e| println!("{e:?}"));
+            .unwrap();
+        assert_eq!(
+            ObjectID::from_str(&admin.id).unwrap(),
+            ObjectID::from_str(&config.GAME_ADMIN_ID).unwrap()
+        );
 
-        println!("admin data: \n{admin:?}\n");
+        let hero = obj
+            .get_sui_obj_first::<SuiHeroObject>(HERO_OBJECT_NAME)
+            .await
+            .unwrap();
+        assert_eq!(hero.level, 1);
 
         let pet = obj
             .get_sui_obj_first::<SuiPetObject>(PET_OBJECT_NAME)
             .await
-            .map_err(|e| println!("{e:?}"));
+            .unwrap();
+        assert_eq!(pet.exp, 0);
 
-        println!("pet data: \n{pet:?}\n");
+        // -- No bot sent yet.
+        let bot = obj.get_sui_obj_first::<SuiBotObject>(BOT_OBJECT_NAME).await;
+        assert!(bot.is_err());
+    }
 
-        let bot = obj
-            .get_sui_obj_first::<SuiBotObject>(BOT_OBJECT_NAME)
-            .await
-            .map_err(|e| println!("{e:?}"));
-        println!("bot data: \n{bot:?}\n");
+    #[tokio::test]
+    async fn test_get_pet_objs_pages_success() {
+        dotenv().ok();
 
-        let hero = obj
-            .get_sui_obj_first::<SuiHeroObject>(HERO_OBJECT_NAME)
-            .await
-            .map_err(|e| println!("{e:?}"));
-        println!("hero data: \n{hero:?}\n");
+        let (sui, executor) = _dev_init::init_sui_for_test();
+        for _ in 0..3 {
+            create_profile(&executor, player()).await.unwrap();
+        }
+
+        let obj = WePetGame::new(sui, player(), &executor.package_id().to_string());
+
+        // -- Walk the pages by hand, 1 pet per page.
+        let mut paged = 0;
+        let mut cursor = None;
+        loop {
+            let page = obj
+                .get_sui_objs_page::<SuiPetObject>(PET_OBJECT_NAME, cursor, 1)
+                .await
+                .unwrap();
+            paged += page.items.len();
+
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        assert_eq!(paged, 3);
+
+        // -- The stream follows the same cursors.
+        let streamed: Vec<_> = obj
+            .get_sui_objs_stream::<SuiPetObject>(PET_OBJECT_NAME)
+            .collect()
+            .await;
+
+        assert_eq!(streamed.len(), paged);
     }
 }
 // endregion:    --- Tests
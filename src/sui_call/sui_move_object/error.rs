@@ -0,0 +1,30 @@
+use serde::Serialize;
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, Serialize, strum_macros::AsRefStr)]
+#[serde(tag = "type", content = "data")]
+pub enum Error {
+    MissingField {
+        field: &'static str,
+    },
+    BadFieldType {
+        field: &'static str,
+        expected: &'static str,
+        found: String,
+    },
+    NumberOutOfRange {
+        field: &'static str,
+        value: String,
+    },
+}
+
+// region:    --- Error Boilerplate
+impl core::fmt::Display for Error {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        write!(fmt, "{self:?}")
+    }
+}
+
+impl std::error::Error for Error {}
+// endregion:    --- Error Boilerplate
@@ -1,11 +1,87 @@
 pub mod admin_obj;
 pub mod bot_obj;
+mod error;
 pub mod hero_obj;
 pub mod pet_obj;
+pub mod value;
+
+pub use self::error::{Error, Result};
+pub use we_pet_macros::FromSuiMoveStruct;
 
 use std::collections::BTreeMap;
 use sui_json_rpc_types::SuiMoveValue;
 
-pub trait FromSuiMoveStruct {
-    fn from_sui_move_struct(fields: BTreeMap<String, SuiMoveValue>) -> Self;
+/// Build a game object from the fields of its Move struct,
+/// usually with `#[derive(FromSuiMoveStruct)]`.
+pub trait FromSuiMoveStruct: Sized {
+    fn from_sui_move_struct(fields: BTreeMap<String, SuiMoveValue>) -> Result<Self>;
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::str::FromStr;
+
+    use sui_json_rpc_types::SuiMoveValue;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+
+    use super::hero_obj::SuiHeroObject;
+    use super::{Error, FromSuiMoveStruct};
+
+    const ID: &str = "0x0000000000000000000000000000000000000000000000000000000000000abc";
+
+    fn hero_fields() -> BTreeMap<String, SuiMoveValue> {
+        BTreeMap::from([
+            (
+                "id".to_string(),
+                SuiMoveValue::UID {
+                    id: ObjectID::from_str(ID).unwrap(),
+                },
+            ),
+            ("level".to_string(), SuiMoveValue::Number(3)),
+            ("hp".to_string(), SuiMoveValue::Number(100)),
+            (
+                "game_id".to_string(),
+                SuiMoveValue::Address(SuiAddress::from_str(ID).unwrap()),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_from_sui_move_struct_ok() {
+        let hero = SuiHeroObject::from_sui_move_struct(hero_fields()).unwrap();
+
+        assert_eq!(hero.id, ID);
+        assert_eq!(hero.level, 3);
+        assert_eq!(hero.game_id, ID);
+    }
+
+    #[test]
+    fn test_from_sui_move_struct_missing_field() {
+        let mut fields = hero_fields();
+        fields.remove("level");
+
+        let res = SuiHeroObject::from_sui_move_struct(fields);
+
+        assert!(matches!(res, Err(Error::MissingField { field: "level" })));
+    }
+
+    #[test]
+    fn test_from_sui_move_struct_bad_field_type() {
+        let mut fields = hero_fields();
+        fields.insert("game_id".to_string(), SuiMoveValue::Number(1));
+
+        let res = SuiHeroObject::from_sui_move_struct(fields);
+
+        assert!(matches!(
+            res,
+            Err(Error::BadFieldType {
+                field: "game_id",
+                expected: "Address",
+                ..
+            })
+        ));
+    }
 }
+// endregion:    --- Tests
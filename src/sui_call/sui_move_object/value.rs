@@ -0,0 +1,63 @@
+//! Field conversions used by `#[derive(FromSuiMoveStruct)]`.
+
+use std::collections::BTreeMap;
+
+use sui_json_rpc_types::SuiMoveValue;
+
+use super::error::{Error, Result};
+
+type FieldMap = BTreeMap<String, SuiMoveValue>;
+
+/// Object id of a `UID` field.
+pub fn uid(fields: &FieldMap, field: &'static str) -> Result<String> {
+    match get(fields, field)? {
+        SuiMoveValue::UID { id } => Ok(id.to_string()),
+        other => Err(bad_type(field, "UID", other)),
+    }
+}
+
+/// An `address` or `ID` field.
+pub fn address(fields: &FieldMap, field: &'static str) -> Result<String> {
+    match get(fields, field)? {
+        SuiMoveValue::Address(address) => Ok(address.to_string()),
+        other => Err(bad_type(field, "Address", other)),
+    }
+}
+
+/// An integer field. Move `u64` and wider come back as decimal strings.
+pub fn number<T: TryFrom<u64>>(fields: &FieldMap, field: &'static str) -> Result<T> {
+    let value = match get(fields, field)? {
+        SuiMoveValue::Number(n) => u64::from(*n),
+        SuiMoveValue::String(s) => s.parse().map_err(|_| Error::BadFieldType {
+            field,
+            expected: "Number",
+            found: format!("String({s})"),
+        })?,
+        other => return Err(bad_type(field, "Number", other)),
+    };
+
+    T::try_from(value).map_err(|_| Error::NumberOutOfRange {
+        field,
+        value: value.to_string(),
+    })
+}
+
+/// A `std::string::String` field.
+pub fn string(fields: &FieldMap, field: &'static str) -> Result<String> {
+    match get(fields, field)? {
+        SuiMoveValue::String(s) => Ok(s.clone()),
+        other => Err(bad_type(field, "String", other)),
+    }
+}
+
+fn get<'a>(fields: &'a FieldMap, field: &'static str) -> Result<&'a SuiMoveValue> {
+    fields.get(field).ok_or(Error::MissingField { field })
+}
+
+fn bad_type(field: &'static str, expected: &'static str, found: &SuiMoveValue) -> Error {
+    Error::BadFieldType {
+        field,
+        expected,
+        found: format!("{found:?}"),
+    }
+}
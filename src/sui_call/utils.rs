@@ -5,14 +5,9 @@ use serde_json::json;
 use sui_json_rpc_types::SuiObjectDataOptions;
 use sui_sdk::SuiClient;
 use sui_types::base_types::{ObjectID, SuiAddress};
-use tracing::debug;
+use tracing::{debug, info};
 
-// pub const SUI_FAUCET: &str = "https://faucet.testnet.sui.io/v1"; // testnet faucet
-
-const SUI_FAUCET: &str = "https://faucet.devnet.sui.io"; // devnet faucet
-
-// local faucet
-// pub const SUI_FAUCET: &str = "http://0.0.0.0:9000";
+use super::network::SuiNetwork;
 
 #[derive(serde::Deserialize)]
 struct FaucetResponse {
@@ -23,7 +18,7 @@ struct FaucetResponse {
 #[allow(unused_assignments)]
 pub async fn request_tokens_from_faucet(
     address: SuiAddress,
-    sui_client: &SuiClient,
+    network: &SuiNetwork,
 ) -> Result<(), anyhow::Error> {
     let address_str = address.to_string();
     let json_body = json![{
@@ -34,7 +29,10 @@ pub async fn request_tokens_from_faucet(
 
     // make the request to the faucet JSON RPC API for coin
     let client = Client::new();
-    let mut url = SUI_FAUCET.to_string();
+    let mut url = network
+        .faucet_url
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("no faucet on sui network {}", network.name))?;
     url.push_str("/gas");
 
     let resp = client
@@ -43,49 +41,12 @@ pub async fn request_tokens_from_faucet(
         .json(&json_body)
         .send()
         .await?;
-    println!(
-        "Faucet request for address {address_str} has status: {}",
+    info!(
+        "{:<12} - {address_str} - status: {}",
+        "FAUCET",
         resp.status()
     );
-    println!("Waiting for the faucet to complete the gas request...");
-
-    println!("{resp:?}");
+    debug!("{:<12} - {resp:?}", "FAUCET");
 
     Ok(())
 }
-
-// region:    --- Tests
-#[cfg(test)]
-mod tests {
-    use std::str::FromStr;
-
-    use dotenvy::dotenv;
-    use serial_test::serial;
-    use sui_sdk::{SuiClient, SuiClientBuilder};
-    use sui_types::base_types::{ObjectID, SuiAddress};
-    use tracing::{debug, info};
-
-    use crate::get_config;
-
-    use super::request_tokens_from_faucet;
-
-    #[serial]
-    #[tokio::test]
-    async fn test_faucet_success() {
-        dotenv().ok();
-
-        let sui_client = SuiClientBuilder::default().build_devnet().await.unwrap();
-
-        let address = SuiAddress::from_str(
-            "0xdb96399b7daeac4613a8494a30cf371206cff2ea4d19924d87ddf151d0d3a1c7",
-        )
-        .unwrap();
-
-        let rs = request_tokens_from_faucet(address, &sui_client)
-            .await
-            .map_err(|e| println!("e is {e:?}"));
-
-        println!("{rs:?}")
-    }
-}
-// endregion:    --- Tests
@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+use super::error::{Error, Result};
+use crate::commands::battle::do_battle;
+use crate::commands::hunt::do_hunt;
+use crate::ctx::Ctx;
+use crate::models::bot::{Bot, BotBmc, BotForCreate};
+use crate::models::ModelManager;
+use crate::sui_call::call_api::create_bot::create_bot;
+use crate::sui_call::call_api::create_profile::create_profile;
+use crate::sui_call::call_api::onboard::{onboard, Onboarding};
+use crate::sui_call::call_api::SuiExecutor;
+use crate::sui_call::read_api::owned_objects::WePetGame;
+use crate::sui_call::sui_move_object::bot_obj::SuiBotObject;
+use crate::sui_call::sui_move_object::hero_obj::SuiHeroObject;
+use crate::sui_call::{BOT_OBJECT_NAME, HERO_OBJECT_NAME};
+use crate::utils::time::unix_timestamp;
+
+/// A game action queued in the outbox, stored as json.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum GameAction {
+    /// Profile, first bot and top-up of a new player in one transaction,
+    /// then save the bot for `user_id`.
+    Onboard {
+        user_id: i64,
+        player: SuiAddress,
+        hp: u8,
+        strength: u8,
+        top_up: Option<u64>,
+    },
+    CreateProfile {
+        player: SuiAddress,
+    },
+    /// Send a bot to `player`, then save it for `user_id`.
+    CreateBot {
+        user_id: i64,
+        player: SuiAddress,
+        hp: u8,
+        strength: u8,
+    },
+    Battle {
+        signer: SuiAddress,
+        pet: ObjectID,
+        bot: ObjectID,
+    },
+    Hunt {
+        signer: SuiAddress,
+        animal: ObjectID,
+        coin: i64,
+    },
+}
+
+/// Digest and result (json) of an executed action, kept in the outbox entry.
+#[derive(Debug, Default)]
+pub struct ActionOutcome {
+    pub digest: Option<String>,
+    pub result: Option<String>,
+}
+
+impl GameAction {
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| Error::BadAction(e.to_string()))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| Error::BadAction(e.to_string()))
+    }
+
+    pub async fn execute(
+        &self,
+        ctx: &Ctx,
+        mm: &ModelManager,
+        executor: &SuiExecutor,
+    ) -> Result<ActionOutcome> {
+        match self {
+            GameAction::Onboard {
+                user_id,
+                player,
+                hp,
+                strength,
+                top_up,
+            } => {
+                // -- Resumed registration, only send the parts not landed yet.
+                let heroes = game_of(executor, *player)
+                    .get_sui_objs::<SuiHeroObject>(HERO_OBJECT_NAME)
+                    .await?;
+                let profile = heroes.is_empty();
+                let bot_id = existing_bot(ctx, mm, executor, *user_id, *player).await?;
+
+                let onboarding = Onboarding {
+                    profile,
+                    bot: bot_id.is_none().then_some((*hp, *strength)),
+                    top_up: top_up.filter(|_| profile),
+                };
+                if onboarding.is_empty() {
+                    return Ok(ActionOutcome {
+                        digest: None,
+                        result: bot_id,
+                    });
+                }
+
+                let onboarded = onboard(executor, *player, &onboarding).await?;
+
+                let bot_id = match onboarded.bot {
+                    Some(bot_id) => {
+                        save_bot(ctx, mm, *user_id, bot_id.to_string()).await?;
+                        Some(bot_id.to_string())
+                    }
+                    None => bot_id,
+                };
+
+                Ok(ActionOutcome {
+                    digest: Some(onboarded.digest.to_string()),
+                    result: bot_id,
+                })
+            }
+            GameAction::CreateProfile { player } => {
+                // -- Resumed registration, the profile may have landed before the failure.
+                let heroes = game_of(executor, *player)
+                    .get_sui_objs::<SuiHeroObject>(HERO_OBJECT_NAME)
+                    .await?;
+                if let Some(hero) = heroes.into_iter().next() {
+                    return Ok(ActionOutcome {
+                        digest: None,
+                        result: Some(hero.id),
+                    });
+                }
+
+                let effects = create_profile(executor, *player).await?;
+
+                Ok(ActionOutcome {
+                    digest: Some(effects.digest.to_string()),
+                    result: None,
+                })
+            }
+            GameAction::CreateBot {
+                user_id,
+                player,
+                hp,
+                strength,
+            } => {
+                // -- Resumed registration, keep the bot already sent to the player.
+                if let Some(bot_id) = existing_bot(ctx, mm, executor, *user_id, *player).await? {
+                    return Ok(ActionOutcome {
+                        digest: None,
+                        result: Some(bot_id),
+                    });
+                }
+
+                let (bot_id, digest) = create_bot(executor, *player, *hp, *strength).await?;
+
+                save_bot(ctx, mm, *user_id, bot_id.to_string()).await?;
+
+                Ok(ActionOutcome {
+                    digest: Some(digest.to_string()),
+                    result: Some(bot_id.to_string()),
+                })
+            }
+            GameAction::Battle { signer, pet, bot } => {
+                let digest = do_battle(executor, *signer, *pet, *bot).await?;
+
+                Ok(ActionOutcome {
+                    digest: Some(digest.to_string()),
+                    result: None,
+                })
+            }
+            GameAction::Hunt {
+                signer,
+                animal,
+                coin,
+            } => {
+                let outcome = do_hunt(executor, *signer, *animal, *coin).await?;
+                let result =
+                    serde_json::to_string(&outcome).map_err(|e| Error::BadAction(e.to_string()))?;
+
+                Ok(ActionOutcome {
+                    digest: Some(outcome.digest.clone()),
+                    result: Some(result),
+                })
+            }
+        }
+    }
+}
+
+fn game_of(executor: &SuiExecutor, player: SuiAddress) -> WePetGame {
+    WePetGame::new(
+        executor.gateway().clone(),
+        player,
+        executor.package_id().to_string().as_str(),
+    )
+}
+
+/// The bot of `user_id`, from the db or else from the chain (then saved).
+async fn existing_bot(
+    ctx: &Ctx,
+    mm: &ModelManager,
+    executor: &SuiExecutor,
+    user_id: i64,
+    player: SuiAddress,
+) -> Result<Option<String>> {
+    let bots = BotBmc::list_by_user::<Bot>(ctx, mm, user_id).await?;
+    if let Some(bot) = bots.into_iter().next() {
+        return Ok(Some(bot.bot_id));
+    }
+
+    let owned = game_of(executor, player)
+        .get_sui_objs::<SuiBotObject>(BOT_OBJECT_NAME)
+        .await?;
+    if let Some(bot) = owned.into_iter().next() {
+        save_bot(ctx, mm, user_id, bot.id.clone()).await?;
+        return Ok(Some(bot.id));
+    }
+
+    Ok(None)
+}
+
+async fn save_bot(ctx: &Ctx, mm: &ModelManager, user_id: i64, bot_id: String) -> Result<()> {
+    BotBmc::create(
+        ctx,
+        mm,
+        BotForCreate {
+            bot_id,
+            user_id,
+            last_creation: unix_timestamp(),
+        },
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use serde::Serialize;
+
+use crate::commands::{self, ClientError};
+use crate::models;
+use crate::sui_call::call_api;
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, Serialize, strum_macros::AsRefStr)]
+#[serde(tag = "type", content = "data")]
+pub enum Error {
+    BadAction(String),
+    SuiRpc(String),
+
+    // -- Modules
+    Command(commands::Error),
+    Model(models::Error),
+    SuiCall(call_api::Error),
+}
+
+// region:    --- Froms
+impl From<commands::Error> for Error {
+    fn from(val: commands::Error) -> Self {
+        Self::Command(val)
+    }
+}
+
+impl From<models::Error> for Error {
+    fn from(val: models::Error) -> Self {
+        Self::Model(val)
+    }
+}
+
+// `sui_call::read_api` returns `anyhow` errors.
+impl From<anyhow::Error> for Error {
+    fn from(val: anyhow::Error) -> Self {
+        Self::SuiRpc(val.to_string())
+    }
+}
+
+impl From<call_api::Error> for Error {
+    fn from(val: call_api::Error) -> Self {
+        Self::SuiCall(val)
+    }
+}
+// endregion: --- Froms
+
+// region:    --- Error Boilerplate
+impl core::fmt::Display for Error {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        write!(fmt, "{self:?}")
+    }
+}
+
+impl std::error::Error for Error {}
+// endregion:    --- Error Boilerplate
+
+// region:    --- Retry
+impl Error {
+    /// Whether another attempt may succeed.
+    ///
+    /// Only failures before the transaction is submitted are retried. A failed
+    /// submission may still have landed on chain, and a game action must not
+    /// run twice.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::SuiCall(e) | Error::Command(commands::Error::SuiCall(e)) => {
+                matches!(
+                    e,
+                    call_api::Error::BuildTransaction(_)
+                        | call_api::Error::DryRun(_)
+                        | call_api::Error::GasCoinUnavailable { .. }
+                )
+            }
+            // -- Object reads before the call.
+            Error::SuiRpc(_) | Error::Command(commands::Error::SuiRpc(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// What the player is told when the action failed for good.
+    pub fn client_error(&self) -> ClientError {
+        match self {
+            Error::Command(e) => e.client_error(),
+            Error::SuiCall(e) => commands::Error::sui_call_client_error(e),
+            _ => ClientError::SERVICE_ERROR,
+        }
+    }
+}
+// endregion: --- Retry
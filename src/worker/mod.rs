@@ -0,0 +1,364 @@
+// region:    --- Modules
+
+mod action;
+mod error;
+mod register;
+
+pub use self::action::{ActionOutcome, GameAction};
+pub use self::error::{Error, Result};
+pub use self::register::{register_steps, resume_register};
+
+use std::time::Duration;
+
+use futures::future::join_all;
+
+use tracing::{debug, error, info};
+
+use crate::ctx::Ctx;
+use crate::get_config;
+use crate::models::audit_log::AuditLogBmc;
+use crate::models::outbox::{OutboxBmc, OutboxEntry, OutboxForCreate, OutboxStatus};
+use crate::models::ModelManager;
+use crate::sui_call::call_api::SuiExecutor;
+use crate::utils::time::unix_timestamp;
+// endregion: --- Modules
+
+// A claimed entry is handed to another worker after this, longer than any move call.
+const LEASE_SEC: i64 = 300;
+
+// region:    --- Enqueue
+
+/// Queue `action` in the outbox, returns the entry id.
+///
+/// `idempotency_key` identifies the request (e.g. the discord interaction),
+/// enqueuing the same key again returns the first entry.
+pub async fn enqueue(
+    ctx: &Ctx,
+    mm: &ModelManager,
+    idempotency_key: String,
+    user_id: Option<i64>,
+    action: &GameAction,
+) -> Result<i64> {
+    let id = OutboxBmc::enqueue(
+        ctx,
+        mm,
+        OutboxForCreate {
+            idempotency_key,
+            user_id,
+            action: action.to_json()?,
+            max_attempts: get_config().OUTBOX_MAX_ATTEMPTS,
+        },
+        unix_timestamp(),
+    )
+    .await?;
+
+    Ok(id)
+}
+
+/// Make sure the action of `idempotency_key` gets done, returns false when it already is.
+///
+/// A missing entry is enqueued and a failed one requeued, for resuming a
+/// request whose action failed for good (e.g. a partial registration).
+pub async fn ensure_enqueued(
+    ctx: &Ctx,
+    mm: &ModelManager,
+    idempotency_key: String,
+    user_id: Option<i64>,
+    action: &GameAction,
+) -> Result<bool> {
+    match OutboxBmc::get_by_key(ctx, mm, &idempotency_key).await? {
+        Some(entry) if entry.is(OutboxStatus::Done) => Ok(false),
+        Some(entry) if entry.is(OutboxStatus::Failed) => {
+            OutboxBmc::requeue(ctx, mm, entry.id, unix_timestamp()).await?;
+            Ok(true)
+        }
+        Some(_) => Ok(true),
+        None => {
+            enqueue(ctx, mm, idempotency_key, user_id, action).await?;
+            Ok(true)
+        }
+    }
+}
+
+/// Poll the entry until it is done or failed, or `timeout` is over.
+/// Returns the last state of the entry.
+pub async fn wait_for(
+    ctx: &Ctx,
+    mm: &ModelManager,
+    id: i64,
+    timeout: Duration,
+) -> Result<OutboxEntry> {
+    let poll_interval = Duration::from_millis(get_config().OUTBOX_POLL_MS);
+    let deadline = tokio::time::Instant::now() + timeout;
+
+    loop {
+        let entry = OutboxBmc::get(ctx, mm, id).await?;
+
+        let finished = entry.is(OutboxStatus::Done) || entry.is(OutboxStatus::Failed);
+        if finished || tokio::time::Instant::now() >= deadline {
+            return Ok(entry);
+        }
+
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+// endregion: --- Enqueue
+
+// region:    --- Worker
+
+/// Executes the outbox entries one at a time, with bounded retries and backoff.
+#[derive(Clone)]
+pub struct Worker {
+    mm: ModelManager,
+    executor: SuiExecutor,
+}
+
+impl Worker {
+    pub fn new(mm: ModelManager, executor: SuiExecutor) -> Self {
+        Worker { mm, executor }
+    }
+
+    /// Run `count` workers side by side, each claims its own entries.
+    pub async fn run_many(self, count: usize) {
+        join_all((0..count.max(1)).map(|_| self.clone().run())).await;
+    }
+
+    /// Process the due entries forever, polling when the outbox is empty.
+    pub async fn run(self) {
+        let poll_interval = Duration::from_millis(get_config().OUTBOX_POLL_MS);
+
+        loop {
+            match self.process_next().await {
+                Ok(true) => continue,
+                Ok(false) => (),
+                Err(e) => error!("{:<12} - {e:?}", "WORKER"),
+            }
+
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    /// Claim and execute the next due entry, returns false when none is due.
+    pub async fn process_next(&self) -> Result<bool> {
+        let ctx = Ctx::root_ctx();
+
+        let Some(entry) =
+            OutboxBmc::claim_next(&ctx, &self.mm, unix_timestamp(), LEASE_SEC).await?
+        else {
+            return Ok(false);
+        };
+
+        let res = match GameAction::from_json(&entry.action) {
+            Ok(action) => {
+                debug!("{:<12} - outbox {} - {action:?}", "WORKER", entry.id);
+                action.execute(&ctx, &self.mm, &self.executor).await
+            }
+            Err(e) => Err(e),
+        };
+
+        let now = unix_timestamp();
+        match res {
+            Ok(outcome) => {
+                info!(
+                    "{:<12} - outbox {} done {:?}",
+                    "WORKER", entry.id, outcome.digest
+                );
+                OutboxBmc::complete(
+                    &ctx,
+                    &self.mm,
+                    entry.id,
+                    outcome.digest.clone(),
+                    outcome.result,
+                    now,
+                )
+                .await?;
+
+                // -- Which player's action made which transaction.
+                if let Some(digest) = outcome.digest {
+                    AuditLogBmc::log_tx(
+                        &ctx,
+                        &self.mm,
+                        entry.user_id,
+                        "outbox",
+                        entry.id.to_string(),
+                        digest,
+                    )
+                    .await?;
+                }
+            }
+            Err(e) => {
+                let config = get_config();
+                let retry_at =
+                    (e.is_retryable() && entry.attempts < entry.max_attempts).then(|| {
+                        now + backoff_sec(
+                            entry.attempts,
+                            config.OUTBOX_BACKOFF_BASE_SEC,
+                            config.OUTBOX_BACKOFF_MAX_SEC,
+                        )
+                    });
+                error!(
+                    "{:<12} - outbox {} attempt {} failed (retry at {retry_at:?}): {e:?}",
+                    "WORKER", entry.id, entry.attempts
+                );
+
+                // The client error goes in `result`, for the command waiting on the entry.
+                let client_error = serde_json::to_string(&e.client_error()).ok();
+                OutboxBmc::fail(
+                    &ctx,
+                    &self.mm,
+                    entry.id,
+                    e.to_string(),
+                    client_error,
+                    retry_at,
+                    now,
+                )
+                .await?;
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+/// Exponential backoff after the attempt number `attempts` (from 1), capped at `max_sec`.
+fn backoff_sec(attempts: i32, base_sec: i64, max_sec: i64) -> i64 {
+    let exp = attempts.saturating_sub(1).clamp(0, 30) as u32;
+
+    base_sec.saturating_mul(1 << exp).min(max_sec)
+}
+// endregion: --- Worker
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use dotenvy::dotenv;
+    use serial_test::serial;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+
+    use super::{backoff_sec, enqueue, GameAction, Worker};
+    use crate::_dev_init;
+    use crate::commands::hunt::HuntOutcome;
+    use crate::ctx::Ctx;
+    use crate::models::bot::{Bot, BotBmc};
+    use crate::models::outbox::{OutboxBmc, OutboxStatus};
+    use crate::models::{UserBmc, UserForCreate};
+    use crate::sui_call::gateway::SuiGateway;
+    use crate::sui_call::read_api::owned_objects::WePetGame;
+    use crate::sui_call::sui_move_object::pet_obj::SuiPetObject;
+    use crate::sui_call::PET_OBJECT_NAME;
+
+    #[test]
+    fn test_backoff_sec_capped() {
+        assert_eq!(backoff_sec(1, 2, 60), 2);
+        assert_eq!(backoff_sec(2, 2, 60), 4);
+        assert_eq!(backoff_sec(4, 2, 60), 16);
+        assert_eq!(backoff_sec(6, 2, 60), 60);
+        assert_eq!(backoff_sec(100, 2, 60), 60);
+    }
+
+    #[test]
+    fn test_game_action_json_roundtrip() {
+        let action = GameAction::Hunt {
+            signer: SuiAddress::from_str(
+                "0xb6c599cba8061a60acc445217823251cc1f0c8b4259a4ec4c8f51be9a8e361aa",
+            )
+            .unwrap(),
+            animal: ObjectID::from_str(
+                "0x0000000000000000000000000000000000000000000000000000000000000abc",
+            )
+            .unwrap(),
+            coin: 3,
+        };
+
+        let json = action.to_json().unwrap();
+
+        assert!(json.contains(r#""type":"Hunt""#));
+        assert_eq!(GameAction::from_json(&json).unwrap(), action);
+    }
+
+    #[serial]
+    #[tokio::test]
+    async fn test_register_battle_hunt_offline() {
+        dotenv().ok();
+
+        let ctx = Ctx::root_ctx();
+        let mm = _dev_init::init_db_for_test().await;
+        let (sui, executor) = _dev_init::init_sui_for_test();
+        let worker = Worker::new(mm.clone(), executor.clone());
+
+        let user_c = UserForCreate {
+            username: Some("test_register_battle_hunt_offline".to_string()),
+            pwd: None,
+            email: None,
+        };
+        let user_id = UserBmc::create(&ctx, &mm, user_c).await.unwrap();
+        let player = SuiAddress::from_str(
+            "0x64f804ad5f8bf531d507a2dd4e00c7de041c8c6ced7744bbe66d93fedf8dfb7f",
+        )
+        .unwrap();
+
+        // -- The registration, one onboarding block run by the worker.
+        let onboard = GameAction::Onboard {
+            user_id,
+            player,
+            hp: 100,
+            strength: 8,
+            top_up: None,
+        };
+        let key = format!("test:register:{user_id}");
+        let id = enqueue(&ctx, &mm, key, Some(user_id), &onboard)
+            .await
+            .unwrap();
+        while worker.process_next().await.unwrap() {}
+
+        let entry = OutboxBmc::get(&ctx, &mm, id).await.unwrap();
+        assert!(entry.is(OutboxStatus::Done));
+        let bots = BotBmc::list_by_user::<Bot>(&ctx, &mm, user_id)
+            .await
+            .unwrap();
+        assert_eq!(bots.len(), 1);
+        let bot = ObjectID::from_str(&bots[0].bot_id).unwrap();
+
+        // -- A resumed onboarding sends nothing twice.
+        let outcome = onboard.execute(&ctx, &mm, &executor).await.unwrap();
+        assert_eq!(outcome.digest, None);
+        assert_eq!(outcome.result, Some(bots[0].bot_id.clone()));
+
+        // -- Battle, then hunt with faucet coins.
+        let game = WePetGame::new(sui.clone(), player, &executor.package_id().to_string());
+        let pet = game
+            .get_sui_obj_first::<SuiPetObject>(PET_OBJECT_NAME)
+            .await
+            .unwrap();
+        let pet = ObjectID::from_str(&pet.id).unwrap();
+
+        sui.request_faucet(player).await.unwrap();
+        GameAction::Battle {
+            signer: player,
+            pet,
+            bot,
+        }
+        .execute(&ctx, &mm, &executor)
+        .await
+        .unwrap();
+        assert!(sui.object_fields(&bot).is_none());
+
+        // -- A second coin, the stake and the gas come from different coins.
+        sui.request_faucet(player).await.unwrap();
+        let outcome = GameAction::Hunt {
+            signer: player,
+            animal: pet,
+            coin: 3,
+        }
+        .execute(&ctx, &mm, &executor)
+        .await
+        .unwrap();
+
+        let hunt: HuntOutcome = serde_json::from_str(&outcome.result.unwrap()).unwrap();
+        assert_eq!(hunt.stake, 3);
+        assert_eq!(hunt.exp_gained, Some(3));
+    }
+}
+// endregion: --- Tests
@@ -0,0 +1,132 @@
+use sui_types::base_types::SuiAddress;
+
+use super::{ensure_enqueued, GameAction, Result};
+use crate::ctx::Ctx;
+use crate::get_config;
+use crate::models::outbox::OutboxBmc;
+use crate::models::ModelManager;
+
+/// Chain steps of a registration, keyed by user so a retry never duplicates them.
+///
+/// One onboarding block (profile, first bot and top-up).
+pub fn register_steps(user_id: i64, player: SuiAddress) -> Vec<(String, GameAction)> {
+    let top_up = get_config().ONBOARD_TOP_UP_MIST;
+
+    vec![(
+        format!("register:onboard:{user_id}"),
+        GameAction::Onboard {
+            user_id,
+            player,
+            hp: 100,
+            strength: 8,
+            top_up: (top_up > 0).then_some(top_up),
+        },
+    )]
+}
+
+/// Steps of the accounts registered before the onboarding block, one call each.
+fn legacy_register_steps(user_id: i64, player: SuiAddress) -> Vec<(String, GameAction)> {
+    vec![
+        (
+            format!("register:profile:{user_id}"),
+            GameAction::CreateProfile { player },
+        ),
+        (
+            format!("register:bot:{user_id}"),
+            GameAction::CreateBot {
+                user_id,
+                player,
+                hp: 100,
+                strength: 8,
+            },
+        ),
+    ]
+}
+
+/// Queue again the chain steps of an account that did not complete them,
+/// returns false when every step is done.
+///
+/// The worker checks the chain before each step, a step that landed before
+/// failing is not run twice.
+pub async fn resume_register(
+    ctx: &Ctx,
+    mm: &ModelManager,
+    user_id: i64,
+    player: SuiAddress,
+) -> Result<bool> {
+    let legacy = OutboxBmc::get_by_key(ctx, mm, &format!("register:profile:{user_id}"))
+        .await?
+        .is_some();
+    let steps = if legacy {
+        legacy_register_steps(user_id, player)
+    } else {
+        register_steps(user_id, player)
+    };
+
+    let mut resumed = false;
+    for (key, action) in steps {
+        resumed |= ensure_enqueued(ctx, mm, key, Some(user_id), &action).await?;
+    }
+
+    Ok(resumed)
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use dotenvy::dotenv;
+    use serial_test::serial;
+    use sui_types::base_types::SuiAddress;
+
+    use super::resume_register;
+    use crate::_dev_init;
+    use crate::ctx::Ctx;
+    use crate::models::outbox::{OutboxBmc, OutboxStatus};
+    use crate::models::{UserBmc, UserForCreate};
+    use crate::utils::time::unix_timestamp;
+
+    #[serial]
+    #[tokio::test]
+    async fn test_resume_register_partial_account() {
+        dotenv().ok();
+
+        let ctx = Ctx::root_ctx();
+        let mm = _dev_init::init_db_for_test().await;
+        let user_c = UserForCreate {
+            username: Some("test_resume_register_partial_account".to_string()),
+            pwd: None,
+            email: None,
+        };
+        let user_id = UserBmc::create(&ctx, &mm, user_c).await.unwrap();
+        let player = SuiAddress::from_str(
+            "0x2b1c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90a",
+        )
+        .unwrap();
+        let key = format!("register:onboard:{user_id}");
+
+        // -- The onboarding failed for good, the account is partial.
+        assert!(resume_register(&ctx, &mm, user_id, player).await.unwrap());
+        let entry = OutboxBmc::get_by_key(&ctx, &mm, &key)
+            .await
+            .unwrap()
+            .unwrap();
+        let now = unix_timestamp();
+        OutboxBmc::fail(&ctx, &mm, entry.id, "rpc down".to_string(), None, None, now)
+            .await
+            .unwrap();
+
+        assert!(resume_register(&ctx, &mm, user_id, player).await.unwrap());
+        let entry = OutboxBmc::get(&ctx, &mm, entry.id).await.unwrap();
+        assert!(entry.is(OutboxStatus::Pending));
+
+        // -- Once onboarded, nothing left to resume.
+        OutboxBmc::complete(&ctx, &mm, entry.id, None, None, now)
+            .await
+            .unwrap();
+
+        assert!(!resume_register(&ctx, &mm, user_id, player).await.unwrap());
+    }
+}
+// endregion: --- Tests